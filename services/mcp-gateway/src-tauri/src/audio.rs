@@ -1,63 +1,241 @@
 use std::sync::mpsc;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+
+use tauri::{AppHandle, Emitter};
 
 /// Messages sent from the main thread to the dedicated audio thread.
-enum AudioMsg {
-    /// Raw PCM-16 LE mono 16kHz bytes to play.
-    Play(Vec<u8>),
-    /// Kill all queued audio immediately (barge-in).
-    Halt,
+pub(crate) enum AudioMsg {
+    /// Raw PCM-16 LE mono 16kHz bytes to play, tagged with an utterance id
+    /// so lifecycle events can be correlated by the caller.
+    Play(Vec<u8>, Option<String>),
+    /// Stop all queued audio (barge-in). When `immediate` is false the sink is
+    /// faded out over ~40 ms first to avoid an audible click.
+    Halt { immediate: bool },
+    /// Lower (or restore) output gain to the target without clearing the queue
+    /// — "lower your voice while I think" ducking.
+    Duck(f32),
+    /// Switch output to the named device, rebuilding the stream + sink.
+    SetDevice(String),
+    /// A single Opus packet (16kHz mono) to decode and play.
+    PlayOpus(Vec<u8>),
+    /// Raw interleaved PCM at an arbitrary rate/channels/format, resampled to
+    /// the output device's rate before playback.
+    PlayPcm {
+        data: Vec<u8>,
+        sample_rate: u32,
+        channels: u16,
+        format: PcmFormat,
+    },
+}
+
+/// Sample encoding accepted by [`AudioMsg::PlayPcm`].
+#[derive(Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PcmFormat {
+    /// Signed 16-bit little-endian.
+    I16,
+    /// 32-bit little-endian float.
+    F32,
+}
+
+/// Upper bound on decoded samples per Opus packet: 120 ms at 16kHz mono — the
+/// largest frame Opus can carry, so packets longer than a single 60 ms frame
+/// still decode into the buffer instead of being dropped.
+const MAX_OPUS_FRAME: usize = 1920;
+
+/// An output or input device as reported by [`list_audio_devices`].
+#[derive(serde::Serialize)]
+pub struct AudioDevice {
+    pub name: String,
+    pub is_default: bool,
+    pub is_input: bool,
 }
 
 /// Thread-safe handle to the native audio thread.
 /// Managed by Tauri's state system — Send + Sync safe.
 pub struct AudioState {
     tx: Mutex<mpsc::Sender<AudioMsg>>,
+    /// Receiver handed to the audio thread by [`AudioState::start`]. The thread
+    /// is spawned lazily so it can be given an [`AppHandle`] for emitting
+    /// playback lifecycle events.
+    rx: Mutex<Option<mpsc::Receiver<AudioMsg>>>,
 }
 
 impl AudioState {
     pub fn new() -> Self {
         let (tx, rx) = mpsc::channel::<AudioMsg>();
-
-        // Spawn a dedicated thread that owns the OS audio output.
-        // OutputStream is !Send, so it must live on a single thread.
-        std::thread::spawn(move || {
-            audio_thread(rx);
-        });
-
         Self {
             tx: Mutex::new(tx),
+            rx: Mutex::new(Some(rx)),
+        }
+    }
+
+    /// Spawn the audio output thread, giving it the app handle it needs to emit
+    /// `playback-started` / `playback-finished` events. Call once at startup.
+    pub fn start(&self, app: AppHandle) {
+        let rx = match self.rx.lock() {
+            Ok(mut guard) => guard.take(),
+            Err(_) => return,
+        };
+        if let Some(rx) = rx {
+            // OutputStream is !Send, so it must live on a single thread.
+            std::thread::spawn(move || audio_thread(rx, app));
         }
     }
+
+    /// Clone the sender so other subsystems (e.g. the mic VAD) can post
+    /// messages — used to trigger automatic barge-in on detected speech.
+    pub(crate) fn sender(&self) -> Result<mpsc::Sender<AudioMsg>, String> {
+        self.tx
+            .lock()
+            .map(|tx| tx.clone())
+            .map_err(|e| format!("Lock poisoned: {e}"))
+    }
 }
 
-/// The audio thread — owns OutputStream + Sink, processes messages forever.
-fn audio_thread(rx: mpsc::Receiver<AudioMsg>) {
-    use rodio::buffer::SamplesBuffer;
+/// Open an output device by name (or the system default when `None`),
+/// returning its stream, handle, and a fresh sink.
+///
+/// The `OutputStream` must be kept alive for as long as the sink is used, so
+/// all three are returned together and owned by the audio thread.
+fn open_output(
+    device_name: Option<&str>,
+) -> Option<(rodio::OutputStream, rodio::OutputStreamHandle, rodio::Sink)> {
+    use cpal::traits::{DeviceTrait, HostTrait};
     use rodio::{OutputStream, Sink};
 
-    let (stream, stream_handle) = match OutputStream::try_default() {
-        Ok(pair) => pair,
-        Err(e) => {
-            eprintln!("[NativeAudio] No audio output device: {e}");
-            return;
+    let (stream, handle) = match device_name {
+        Some(name) => {
+            let host = cpal::default_host();
+            let device = host
+                .output_devices()
+                .ok()
+                .and_then(|mut ds| ds.find(|d| d.name().map(|n| n == name).unwrap_or(false)));
+            match device {
+                Some(d) => match OutputStream::try_from_device(&d) {
+                    Ok(pair) => pair,
+                    Err(e) => {
+                        eprintln!("[NativeAudio] Cannot open '{name}': {e}; using default.");
+                        OutputStream::try_default().ok()?
+                    }
+                },
+                None => {
+                    eprintln!("[NativeAudio] Device '{name}' not found; using default.");
+                    OutputStream::try_default().ok()?
+                }
+            }
         }
+        None => OutputStream::try_default().ok()?,
     };
 
-    let mut sink = match Sink::try_new(&stream_handle) {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("[NativeAudio] Failed to create sink: {e}");
+    let sink = Sink::try_new(&handle).ok()?;
+    Some((stream, handle, sink))
+}
+
+/// Linearly ramp the sink's volume down to zero over ~40 ms so an interrupted
+/// utterance tapers off instead of clicking. The caller restores volume by
+/// rebuilding the sink (a fresh sink defaults to gain 1.0).
+fn fade_out(sink: &rodio::Sink) {
+    const STEPS: u32 = 8;
+    let step = std::time::Duration::from_millis(5);
+    let start = sink.volume();
+    for i in (0..STEPS).rev() {
+        sink.set_volume(start * i as f32 / STEPS as f32);
+        std::thread::sleep(step);
+    }
+}
+
+/// Decode interleaved PCM bytes into `f32` samples in `[-1.0, 1.0]`.
+fn decode_pcm(data: &[u8], format: PcmFormat) -> Vec<f32> {
+    match format {
+        PcmFormat::I16 => data
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+            .collect(),
+        PcmFormat::F32 => data
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+    }
+}
+
+/// Resample interleaved `f32` audio from `from_rate` to `to_rate` using linear
+/// interpolation, preserving channel interleaving.
+fn resample_linear(samples: &[f32], channels: u16, from_rate: u32, to_rate: u32) -> Vec<f32> {
+    let channels = channels.max(1) as usize;
+    if from_rate == to_rate || samples.len() < channels {
+        return samples.to_vec();
+    }
+    let in_frames = samples.len() / channels;
+    let ratio = to_rate as f64 / from_rate as f64;
+    let out_frames = ((in_frames as f64) * ratio).round() as usize;
+    let mut out = Vec::with_capacity(out_frames * channels);
+
+    for frame in 0..out_frames {
+        // Position in the source timeline, in fractional frames.
+        let src = frame as f64 / ratio;
+        let i = src.floor() as usize;
+        let frac = (src - i as f64) as f32;
+        for ch in 0..channels {
+            let a = samples[i * channels + ch];
+            let b = if i + 1 < in_frames {
+                samples[(i + 1) * channels + ch]
+            } else {
+                a
+            };
+            out.push(a + (b - a) * frac);
+        }
+    }
+    out
+}
+
+/// The output device's preferred sample rate, or 48kHz as a safe default.
+fn preferred_output_rate() -> u32 {
+    use cpal::traits::{DeviceTrait, HostTrait};
+    cpal::default_host()
+        .default_output_device()
+        .and_then(|d| d.default_output_config().ok())
+        .map(|c| c.sample_rate().0)
+        .unwrap_or(48_000)
+}
+
+/// The audio thread — owns OutputStream + Sink, processes messages forever.
+fn audio_thread(rx: mpsc::Receiver<AudioMsg>, app: AppHandle) {
+    use rodio::buffer::SamplesBuffer;
+    use rodio::Sink;
+    use std::time::Duration;
+
+    let (mut _stream, mut stream_handle, mut sink) = match open_output(None) {
+        Some(ctx) => ctx,
+        None => {
+            eprintln!("[NativeAudio] No audio output device.");
             return;
         }
     };
 
-    // Keep _stream alive for the lifetime of the thread
-    let _stream = stream;
+    // Persistent Opus decoder — kept across packets so inter-frame state is
+    // preserved for streamed TTS. Decoded samples land in a reusable buffer.
+    let mut opus_decoder = opus::Decoder::new(16000, opus::Channels::Mono).ok();
+    let mut opus_buf = vec![0i16; MAX_OPUS_FRAME];
+
+    // Utterance currently draining through the sink, if any. Used to emit the
+    // `playback-finished` event when the sink transitions back to empty.
+    let mut current_utterance: Option<String> = None;
+    let mut auto_seq: u64 = 0;
+
+    // Output device rate used as the resampling target for arbitrary PCM.
+    let target_rate = preferred_output_rate();
 
     loop {
-        match rx.recv() {
-            Ok(AudioMsg::Play(bytes)) => {
+        // Poll rather than block so we can notice the sink draining.
+        let msg = match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(m) => Some(m),
+            Err(mpsc::RecvTimeoutError::Timeout) => None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+
+        match msg {
+            Some(AudioMsg::Play(bytes, utterance_id)) => {
                 if bytes.len() < 2 {
                     continue;
                 }
@@ -69,9 +247,45 @@ fn audio_thread(rx: mpsc::Receiver<AudioMsg>) {
 
                 let buffer = SamplesBuffer::new(1, 16000, samples);
                 sink.append(buffer);
+
+                let id = utterance_id.unwrap_or_else(|| {
+                    auto_seq += 1;
+                    format!("utt-{auto_seq}")
+                });
+                current_utterance = Some(id.clone());
+                let _ = app.emit("playback-started", id);
+            }
+            Some(AudioMsg::PlayOpus(packet)) => {
+                let decoder = match opus_decoder.as_mut() {
+                    Some(d) => d,
+                    None => continue,
+                };
+                match decoder.decode(&packet, &mut opus_buf, false) {
+                    Ok(n) => {
+                        let buffer = SamplesBuffer::new(1, 16000, opus_buf[..n].to_vec());
+                        sink.append(buffer);
+                        // Streamed TTS arrives as many packets; bracket the
+                        // whole stream with one utterance so the frontend gets
+                        // `playback-started` on the first packet and
+                        // `playback-finished` when the sink drains.
+                        if current_utterance.is_none() {
+                            auto_seq += 1;
+                            let id = format!("utt-{auto_seq}");
+                            current_utterance = Some(id.clone());
+                            let _ = app.emit("playback-started", id);
+                        }
+                    }
+                    Err(e) => {
+                        // Skip the bad packet rather than killing the thread.
+                        eprintln!("[NativeAudio] Opus decode error: {e}");
+                    }
+                }
             }
-            Ok(AudioMsg::Halt) => {
-                // Stop current playback and create a fresh sink
+            Some(AudioMsg::Halt { immediate }) => {
+                // Fade the sink out first unless an instant kill was requested.
+                if !immediate {
+                    fade_out(&sink);
+                }
                 sink.stop();
                 sink = match Sink::try_new(&stream_handle) {
                     Ok(s) => s,
@@ -80,10 +294,52 @@ fn audio_thread(rx: mpsc::Receiver<AudioMsg>) {
                         return;
                     }
                 };
+                // A barge-in ends the utterance without a natural drain.
+                if let Some(id) = current_utterance.take() {
+                    let _ = app.emit("playback-finished", id);
+                }
             }
-            Err(_) => {
-                // Channel closed — app is shutting down
-                break;
+            Some(AudioMsg::PlayPcm {
+                data,
+                sample_rate,
+                channels,
+                format,
+            }) => {
+                let decoded = decode_pcm(&data, format);
+                if decoded.is_empty() {
+                    continue;
+                }
+                let samples = resample_linear(&decoded, channels, sample_rate, target_rate);
+                let buffer = SamplesBuffer::new(channels.max(1), target_rate, samples);
+                sink.append(buffer);
+
+                auto_seq += 1;
+                let id = format!("utt-{auto_seq}");
+                current_utterance = Some(id.clone());
+                let _ = app.emit("playback-started", id);
+            }
+            Some(AudioMsg::Duck(gain)) => {
+                sink.set_volume(gain.clamp(0.0, 1.0));
+            }
+            Some(AudioMsg::SetDevice(name)) => {
+                // Drop the current stream/sink and rebuild on the chosen device.
+                match open_output(Some(&name)) {
+                    Some((stream, handle, new_sink)) => {
+                        _stream = stream;
+                        stream_handle = handle;
+                        sink = new_sink;
+                        eprintln!("[NativeAudio] Switched output to '{name}'.");
+                    }
+                    None => eprintln!("[NativeAudio] Failed to switch to '{name}'."),
+                }
+            }
+            None => {}
+        }
+
+        // Emit `playback-finished` once the queue drains naturally.
+        if current_utterance.is_some() && sink.empty() {
+            if let Some(id) = current_utterance.take() {
+                let _ = app.emit("playback-finished", id);
             }
         }
     }
@@ -97,18 +353,380 @@ fn audio_thread(rx: mpsc::Receiver<AudioMsg>) {
 pub fn play_native_audio(
     state: tauri::State<'_, AudioState>,
     audio_bytes: Vec<u8>,
+    utterance_id: Option<String>,
 ) -> Result<(), String> {
     let tx = state.tx.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
-    tx.send(AudioMsg::Play(audio_bytes))
+    tx.send(AudioMsg::Play(audio_bytes, utterance_id))
         .map_err(|e| format!("Audio thread dead: {e}"))?;
     Ok(())
 }
 
-/// Instantly kill all queued audio — the barge-in kill switch.
+/// Play raw interleaved PCM at an arbitrary sample rate / channel count /
+/// format, resampling to the output device's rate as needed.
+///
+/// [`play_native_audio`] remains the thin 16kHz/mono/i16 wrapper for the common
+/// TTS path; this command handles higher-quality voices and notification sounds.
 #[tauri::command]
-pub fn halt_native_audio(state: tauri::State<'_, AudioState>) -> Result<(), String> {
+pub fn play_native_audio_pcm(
+    state: tauri::State<'_, AudioState>,
+    data: Vec<u8>,
+    sample_rate: u32,
+    channels: u16,
+    format: PcmFormat,
+) -> Result<(), String> {
     let tx = state.tx.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
-    tx.send(AudioMsg::Halt)
+    tx.send(AudioMsg::PlayPcm {
+        data,
+        sample_rate,
+        channels,
+        format,
+    })
+    .map_err(|e| format!("Audio thread dead: {e}"))?;
+    Ok(())
+}
+
+/// Decode and play a single Opus packet (16kHz mono).
+///
+/// Lets the network layer stream compressed TTS end-to-end while reusing the
+/// existing sink and barge-in machinery.
+#[tauri::command]
+pub fn play_native_audio_opus(
+    state: tauri::State<'_, AudioState>,
+    packet: Vec<u8>,
+) -> Result<(), String> {
+    let tx = state.tx.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    tx.send(AudioMsg::PlayOpus(packet))
+        .map_err(|e| format!("Audio thread dead: {e}"))?;
+    Ok(())
+}
+
+/// Stop all queued audio — the barge-in kill switch.
+///
+/// By default the sink is faded out over ~40 ms to avoid a click;
+/// pass `immediate = true` when absolute silence is required at once.
+#[tauri::command]
+pub fn halt_native_audio(
+    state: tauri::State<'_, AudioState>,
+    immediate: Option<bool>,
+) -> Result<(), String> {
+    let tx = state.tx.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    tx.send(AudioMsg::Halt {
+        immediate: immediate.unwrap_or(false),
+    })
+    .map_err(|e| format!("Audio thread dead: {e}"))?;
+    Ok(())
+}
+
+/// Lower output gain to `gain` (0.0–1.0) without clearing the queue.
+#[tauri::command]
+pub fn duck_native_audio(state: tauri::State<'_, AudioState>, gain: f32) -> Result<(), String> {
+    let tx = state.tx.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    tx.send(AudioMsg::Duck(gain))
+        .map_err(|e| format!("Audio thread dead: {e}"))?;
+    Ok(())
+}
+
+/// Restore output gain to full after a [`duck_native_audio`] call.
+#[tauri::command]
+pub fn restore_native_audio(state: tauri::State<'_, AudioState>) -> Result<(), String> {
+    let tx = state.tx.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    tx.send(AudioMsg::Duck(1.0))
         .map_err(|e| format!("Audio thread dead: {e}"))?;
     Ok(())
 }
+
+/// Enumerate output and input devices so the frontend can offer a picker.
+#[tauri::command]
+pub fn list_audio_devices() -> Vec<AudioDevice> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let host = cpal::default_host();
+    let mut devices = Vec::new();
+
+    let default_out = host
+        .default_output_device()
+        .and_then(|d| d.name().ok());
+    if let Ok(outs) = host.output_devices() {
+        for d in outs {
+            if let Ok(name) = d.name() {
+                devices.push(AudioDevice {
+                    is_default: Some(&name) == default_out.as_ref(),
+                    name,
+                    is_input: false,
+                });
+            }
+        }
+    }
+
+    let default_in = host.default_input_device().and_then(|d| d.name().ok());
+    if let Ok(ins) = host.input_devices() {
+        for d in ins {
+            if let Ok(name) = d.name() {
+                devices.push(AudioDevice {
+                    is_default: Some(&name) == default_in.as_ref(),
+                    name,
+                    is_input: true,
+                });
+            }
+        }
+    }
+
+    devices
+}
+
+/// Path of the file remembering the chosen output device.
+fn device_pref_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    use tauri::Manager;
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Cannot resolve app config dir: {e}"))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Cannot create config dir: {e}"))?;
+    Ok(dir.join("audio-device.txt"))
+}
+
+/// Select the output device by name, applying it now and persisting the choice
+/// so it survives restarts.
+#[tauri::command]
+pub fn select_audio_device(
+    app: AppHandle,
+    state: tauri::State<'_, AudioState>,
+    name: String,
+) -> Result<(), String> {
+    std::fs::write(device_pref_path(&app)?, &name)
+        .map_err(|e| format!("Cannot persist device choice: {e}"))?;
+    let tx = state.tx.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    tx.send(AudioMsg::SetDevice(name))
+        .map_err(|e| format!("Audio thread dead: {e}"))?;
+    Ok(())
+}
+
+/// Re-apply the persisted output-device choice at startup, if any.
+pub fn apply_saved_device(app: &AppHandle, state: &AudioState) {
+    let path = match device_pref_path(app) {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+    if let Ok(name) = std::fs::read_to_string(&path) {
+        let name = name.trim().to_string();
+        if !name.is_empty() {
+            if let Ok(tx) = state.tx.lock() {
+                let _ = tx.send(AudioMsg::SetDevice(name));
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Microphone capture + voice-activity detection (automatic barge-in)
+// ---------------------------------------------------------------------------
+
+/// Tunable parameters for the energy-based VAD. Shared with the capture
+/// callback behind a mutex so the `set_vad_*` commands can adjust them live.
+#[derive(Clone)]
+struct VadConfig {
+    /// Absolute RMS floor; speech is never declared below this even if the
+    /// adaptive noise-floor estimate drifts very low.
+    floor: f32,
+    /// Multiplier applied to the noise-floor estimate to form the speech
+    /// threshold (`noise_floor * factor`).
+    factor: f32,
+    /// Consecutive above-threshold frames required to declare speech
+    /// (~100 ms of audio at typical callback sizes).
+    onset_frames: u32,
+    /// Consecutive sub-threshold frames required to declare end-of-speech
+    /// (~500 ms hangover, so short pauses don't chop an utterance).
+    hangover_frames: u32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            floor: 0.01,
+            factor: 3.0,
+            onset_frames: 3,
+            hangover_frames: 15,
+        }
+    }
+}
+
+/// Owns the microphone input thread and the live VAD configuration.
+/// Managed by Tauri's state system alongside [`AudioState`].
+pub struct MicState {
+    config: Arc<Mutex<VadConfig>>,
+}
+
+impl MicState {
+    pub fn new() -> Self {
+        Self {
+            config: Arc::new(Mutex::new(VadConfig::default())),
+        }
+    }
+
+    /// Start the mic capture thread. Emits `speech-start` / `speech-end` /
+    /// `mic-level` events and posts [`AudioMsg::Halt`] on speech onset so the
+    /// user can interrupt the assistant simply by speaking.
+    pub fn start(&self, app: AppHandle, audio_tx: mpsc::Sender<AudioMsg>) {
+        let config = Arc::clone(&self.config);
+        std::thread::spawn(move || mic_thread(app, audio_tx, config));
+    }
+}
+
+/// Running VAD state, advanced once per capture callback.
+struct Vad {
+    app: AppHandle,
+    audio_tx: mpsc::Sender<AudioMsg>,
+    config: Arc<Mutex<VadConfig>>,
+    noise_floor: f32,
+    active: bool,
+    onset: u32,
+    silence: u32,
+}
+
+impl Vad {
+    /// Process one frame (one capture callback's worth of samples).
+    fn process(&mut self, samples: &[f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        let cfg = match self.config.lock() {
+            Ok(c) => c.clone(),
+            Err(_) => return,
+        };
+        let _ = self.app.emit("mic-level", rms);
+
+        let threshold = (self.noise_floor * cfg.factor).max(cfg.floor);
+        if rms > threshold {
+            self.silence = 0;
+            self.onset += 1;
+            if !self.active && self.onset >= cfg.onset_frames {
+                self.active = true;
+                let _ = self.app.emit("speech-start", rms);
+                // Automatic barge-in: fade out any ongoing playback.
+                let _ = self.audio_tx.send(AudioMsg::Halt { immediate: false });
+            }
+        } else {
+            self.onset = 0;
+            // Adapt the noise floor only while no speech is present.
+            self.noise_floor = self.noise_floor * 0.95 + rms * 0.05;
+            if self.active {
+                self.silence += 1;
+                if self.silence >= cfg.hangover_frames {
+                    self.active = false;
+                    let _ = self.app.emit("speech-end", rms);
+                }
+            }
+        }
+    }
+}
+
+/// Mic capture thread — owns the cpal input stream for its entire lifetime.
+fn mic_thread(app: AppHandle, audio_tx: mpsc::Sender<AudioMsg>, config: Arc<Mutex<VadConfig>>) {
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+    let host = cpal::default_host();
+    let device = match host.default_input_device() {
+        Some(d) => d,
+        None => {
+            eprintln!("[Mic] No input device available.");
+            return;
+        }
+    };
+    let supported = match device.default_input_config() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("[Mic] No default input config: {e}");
+            return;
+        }
+    };
+    let sample_format = supported.sample_format();
+    let stream_config: cpal::StreamConfig = supported.into();
+
+    let mut vad = Vad {
+        app,
+        audio_tx,
+        config,
+        noise_floor: 0.001,
+        active: false,
+        onset: 0,
+        silence: 0,
+    };
+    let err_fn = |e| eprintln!("[Mic] Input stream error: {e}");
+
+    // cpal delivers i16/u16 or f32 depending on the device; normalize to f32.
+    let stream = match sample_format {
+        cpal::SampleFormat::F32 => device.build_input_stream(
+            &stream_config,
+            move |data: &[f32], _: &_| vad.process(data),
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::I16 => device.build_input_stream(
+            &stream_config,
+            move |data: &[i16], _: &_| {
+                let f: Vec<f32> = data.iter().map(|s| *s as f32 / i16::MAX as f32).collect();
+                vad.process(&f);
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::U16 => device.build_input_stream(
+            &stream_config,
+            move |data: &[u16], _: &_| {
+                let f: Vec<f32> = data
+                    .iter()
+                    .map(|s| (*s as f32 - u16::MAX as f32 / 2.0) / (u16::MAX as f32 / 2.0))
+                    .collect();
+                vad.process(&f);
+            },
+            err_fn,
+            None,
+        ),
+        other => {
+            eprintln!("[Mic] Unsupported sample format: {other:?}");
+            return;
+        }
+    };
+
+    let stream = match stream {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("[Mic] Failed to build input stream: {e}");
+            return;
+        }
+    };
+    if let Err(e) = stream.play() {
+        eprintln!("[Mic] Failed to start input stream: {e}");
+        return;
+    }
+
+    // The stream runs on cpal's own thread; keep it alive by parking here.
+    loop {
+        std::thread::park();
+    }
+}
+
+/// Set the absolute RMS floor used as a minimum speech threshold.
+#[tauri::command]
+pub fn set_vad_threshold(state: tauri::State<'_, MicState>, floor: f32) -> Result<(), String> {
+    let mut cfg = state.config.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    cfg.floor = floor;
+    Ok(())
+}
+
+/// Set the noise-floor multiplier that forms the adaptive speech threshold.
+#[tauri::command]
+pub fn set_vad_factor(state: tauri::State<'_, MicState>, factor: f32) -> Result<(), String> {
+    let mut cfg = state.config.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    cfg.factor = factor;
+    Ok(())
+}
+
+/// Set the end-of-speech hangover, in capture frames.
+#[tauri::command]
+pub fn set_vad_hangover(state: tauri::State<'_, MicState>, frames: u32) -> Result<(), String> {
+    let mut cfg = state.config.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    cfg.hangover_frames = frames;
+    Ok(())
+}
@@ -8,6 +8,7 @@
 //! running (e.g. via `npm run dev`), the health check detects them and
 //! skips spawning — no double-spawn.
 
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use std::process::{Child, Command};
 use std::time::Duration;
@@ -15,6 +16,21 @@ use std::time::Duration;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager};
 
+/// Maximum number of recent log lines retained per backend in the ring buffer.
+const LOG_RING_CAPACITY: usize = 500;
+
+/// One captured line of child output, tagged with its source service and level.
+#[derive(Clone, Serialize)]
+pub struct BackendLogLine {
+    /// `engine` or `litellm`.
+    pub service: String,
+    /// `info` (stdout) or `error` (stderr).
+    pub level: String,
+    pub line: String,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u128,
+}
+
 // ---------------------------------------------------------------------------
 // Public state shared via Tauri's managed state
 // ---------------------------------------------------------------------------
@@ -42,6 +58,8 @@ pub struct BackendState {
     pub status: Mutex<BackendStatus>,
     engine_process: Mutex<Option<Child>>,
     litellm_process: Mutex<Option<Child>>,
+    /// Bounded ring buffer of recent child log lines (both services).
+    logs: Mutex<VecDeque<BackendLogLine>>,
 }
 
 impl BackendState {
@@ -50,10 +68,83 @@ impl BackendState {
             status: Mutex::new(BackendStatus::default()),
             engine_process: Mutex::new(None),
             litellm_process: Mutex::new(None),
+            logs: Mutex::new(VecDeque::with_capacity(LOG_RING_CAPACITY)),
         }
     }
 }
 
+/// True unless forwarding has been suppressed via `VOCO_BACKEND_LOG=off`.
+fn backend_log_enabled() -> bool {
+    std::env::var("VOCO_BACKEND_LOG")
+        .map(|v| v.to_ascii_lowercase() != "off")
+        .unwrap_or(true)
+}
+
+fn now_millis() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// Spawn reader threads that drain `child`'s stdout/stderr line by line,
+/// pushing each into the ring buffer and emitting a `backend-log` event.
+///
+/// Draining the pipes is mandatory even when forwarding is disabled: an unread
+/// pipe eventually fills and blocks the child.
+fn attach_log_readers(app: &AppHandle, state: &Arc<BackendState>, child: &mut Child, service: &str) {
+    use std::io::{BufRead, BufReader};
+
+    let mut spawn_reader = |reader: Option<Box<dyn std::io::Read + Send>>, level: &'static str| {
+        let reader = match reader {
+            Some(r) => r,
+            None => return,
+        };
+        let app = app.clone();
+        let state = Arc::clone(state);
+        let service = service.to_string();
+        std::thread::spawn(move || {
+            let buf = BufReader::new(reader);
+            for line in buf.lines().map_while(Result::ok) {
+                let entry = BackendLogLine {
+                    service: service.clone(),
+                    level: level.to_string(),
+                    line,
+                    timestamp: now_millis(),
+                };
+                if let Ok(mut logs) = state.logs.lock() {
+                    if logs.len() >= LOG_RING_CAPACITY {
+                        logs.pop_front();
+                    }
+                    logs.push_back(entry.clone());
+                }
+                if backend_log_enabled() {
+                    let _ = app.emit("backend-log", entry);
+                }
+            }
+        });
+    };
+
+    spawn_reader(
+        child.stdout.take().map(|s| Box::new(s) as Box<dyn std::io::Read + Send>),
+        "info",
+    );
+    spawn_reader(
+        child.stderr.take().map(|s| Box::new(s) as Box<dyn std::io::Read + Send>),
+        "error",
+    );
+}
+
+/// Return the recent backend log lines captured in the ring buffer.
+#[tauri::command]
+pub fn get_backend_logs(state: tauri::State<'_, Arc<BackendState>>) -> Vec<BackendLogLine> {
+    state
+        .logs
+        .lock()
+        .map(|logs| logs.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
 // ---------------------------------------------------------------------------
 // Tauri command — frontend polls this to know when to connect
 // ---------------------------------------------------------------------------
@@ -170,7 +261,8 @@ pub fn start_services(app: AppHandle, state: Arc<BackendState>) {
     // --- Spawn LiteLLM proxy ---
     let litellm_result = spawn_litellm(&engine_dir);
     match litellm_result {
-        Ok(child) => {
+        Ok(mut child) => {
+            attach_log_readers(&app, &state, &mut child, "litellm");
             *state.litellm_process.lock().unwrap() = Some(child);
             eprintln!("[Backend] LiteLLM proxy spawned.");
         }
@@ -183,7 +275,8 @@ pub fn start_services(app: AppHandle, state: Arc<BackendState>) {
     // --- Spawn cognitive-engine ---
     let engine_result = spawn_engine(&engine_dir);
     match engine_result {
-        Ok(child) => {
+        Ok(mut child) => {
+            attach_log_readers(&app, &state, &mut child, "engine");
             *state.engine_process.lock().unwrap() = Some(child);
             eprintln!("[Backend] Cognitive-engine spawned.");
         }
@@ -194,30 +287,265 @@ pub fn start_services(app: AppHandle, state: Arc<BackendState>) {
         }
     }
 
-    // --- Poll health endpoints ---
+    // --- Poll health endpoints, then hand off to the supervisor ---
     let state_clone = Arc::clone(&state);
     let app_clone = app.clone();
+    let supervisor_dir = engine_dir.clone();
     std::thread::spawn(move || {
-        poll_health_blocking(&state_clone, 60);
+        poll_health_blocking(&app_clone, &state_clone, &supervisor_dir, 60);
         let _ = app_clone.emit("backend-ready", ());
+        // Keep watching for the rest of the process lifetime.
+        supervise(app_clone, state_clone, supervisor_dir);
     });
 }
 
+/// Seconds between supervisor health sweeps (env `VOCO_HEALTH_INTERVAL`).
+fn health_interval_secs() -> u64 {
+    std::env::var("VOCO_HEALTH_INTERVAL")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10)
+}
+
+/// Seconds a service must stay continuously unhealthy before it is restarted
+/// (env `VOCO_UNHEALTHY_TIMEOUT`).  A single transient 500 won't bounce it.
+fn unhealthy_timeout_secs() -> u64 {
+    std::env::var("VOCO_UNHEALTHY_TIMEOUT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(35)
+}
+
+/// Long-lived supervisor: periodically re-checks each service's health and
+/// liveness and restarts one that has been unhealthy past the timeout.
+///
+/// Mirrors the doctor-restart pattern — it tracks how long each service has
+/// been continuously unhealthy and only restarts once that exceeds the
+/// unhealthy timeout, so flapping doesn't cause restart storms.
+fn supervise(app: AppHandle, state: Arc<BackendState>, engine_dir: std::path::PathBuf) {
+    let interval = Duration::from_secs(health_interval_secs());
+    let timeout = Duration::from_secs(unhealthy_timeout_secs());
+
+    // Per-service instant at which it first went unhealthy (None while healthy).
+    let mut engine_unhealthy_since: Option<std::time::Instant> = None;
+    let mut litellm_unhealthy_since: Option<std::time::Instant> = None;
+
+    loop {
+        std::thread::sleep(interval);
+
+        supervise_one(
+            &app,
+            &state,
+            &engine_dir,
+            Service::Engine,
+            &mut engine_unhealthy_since,
+            timeout,
+        );
+        supervise_one(
+            &app,
+            &state,
+            &engine_dir,
+            Service::Litellm,
+            &mut litellm_unhealthy_since,
+            timeout,
+        );
+    }
+}
+
+/// Which backend service a supervisor action targets.
+#[derive(Clone, Copy)]
+enum Service {
+    Engine,
+    Litellm,
+}
+
+impl Service {
+    fn label(self) -> &'static str {
+        match self {
+            Service::Engine => "engine",
+            Service::Litellm => "litellm",
+        }
+    }
+
+    fn health_url(self) -> &'static str {
+        match self {
+            Service::Engine => "http://127.0.0.1:8001/health",
+            Service::Litellm => "http://127.0.0.1:4000/health",
+        }
+    }
+}
+
+/// Check one service and restart it if it has exceeded the unhealthy timeout.
+fn supervise_one(
+    app: &AppHandle,
+    state: &Arc<BackendState>,
+    engine_dir: &std::path::Path,
+    service: Service,
+    unhealthy_since: &mut Option<std::time::Instant>,
+    timeout: Duration,
+) {
+    // A service counts as down if its health endpoint fails OR its child died.
+    let child_alive = child_is_alive(state, service);
+    let healthy = child_alive && check_health_sync(service.health_url());
+
+    if healthy {
+        *unhealthy_since = None;
+        return;
+    }
+
+    let since = unhealthy_since.get_or_insert_with(std::time::Instant::now);
+    if since.elapsed() < timeout {
+        return; // still inside the grace window
+    }
+
+    eprintln!(
+        "[Backend] {} unhealthy for {:?} — restarting.",
+        service.label(),
+        since.elapsed()
+    );
+    let _ = app.emit("backend-restarting", service.label());
+    restart_service(app, state, engine_dir, service);
+    *unhealthy_since = None;
+}
+
+/// True if the stored child for `service` is still running (or no child is
+/// tracked, e.g. the service runs externally).
+fn child_is_alive(state: &Arc<BackendState>, service: Service) -> bool {
+    let slot = match service {
+        Service::Engine => &state.engine_process,
+        Service::Litellm => &state.litellm_process,
+    };
+    let mut guard = match slot.lock() {
+        Ok(g) => g,
+        Err(_) => return true,
+    };
+    match guard.as_mut() {
+        Some(child) => matches!(child.try_wait(), Ok(None)),
+        None => true,
+    }
+}
+
+/// True if no child is tracked for `service` — either its initial spawn failed
+/// or it was never started. Distinct from [`child_is_alive`] (which treats an
+/// empty slot as "alive") so the health poller can retry a service that never
+/// came up at all, not just one that started and then died.
+fn child_slot_empty(state: &Arc<BackendState>, service: Service) -> bool {
+    let slot = match service {
+        Service::Engine => &state.engine_process,
+        Service::Litellm => &state.litellm_process,
+    };
+    match slot.lock() {
+        Ok(guard) => guard.is_none(),
+        Err(_) => false,
+    }
+}
+
+/// Respawn a service, replace its stored `Child`, and reset its ready flag.
+fn restart_service(
+    app: &AppHandle,
+    state: &Arc<BackendState>,
+    engine_dir: &std::path::Path,
+    service: Service,
+) {
+    let spawned = match service {
+        Service::Engine => spawn_engine(engine_dir),
+        Service::Litellm => spawn_litellm(engine_dir),
+    };
+    match spawned {
+        Ok(mut child) => {
+            attach_log_readers(app, state, &mut child, service.label());
+            let slot = match service {
+                Service::Engine => &state.engine_process,
+                Service::Litellm => &state.litellm_process,
+            };
+            if let Ok(mut guard) = slot.lock() {
+                // Reap the previous child (and its worker tree) before replacing it.
+                if let Some(mut old) = guard.take() {
+                    kill_process_tree(&mut old);
+                }
+                *guard = Some(child);
+            }
+            let mut s = state.status.lock().unwrap();
+            match service {
+                Service::Engine => s.engine_ready = false,
+                Service::Litellm => s.litellm_ready = false,
+            }
+        }
+        Err(e) => {
+            eprintln!("[Backend] Failed to restart {}: {e}", service.label());
+            state.status.lock().unwrap().error = Some(format!("{} restart failed: {e}", service.label()));
+        }
+    }
+}
+
+/// Put a child in its own process group so its whole worker tree can be reaped
+/// together on shutdown.
+///
+/// On Unix this calls `setsid()` in the forked child via `pre_exec`, making it
+/// a session/group leader (PGID == PID).  On Windows it requests a new process
+/// group; the matching tree-kill happens in [`kill_process_tree`].
+fn new_process_group(cmd: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: `setsid` is async-signal-safe and the only call we make in
+        // the forked child before exec.
+        unsafe {
+            cmd.pre_exec(|| {
+                libc::setsid();
+                Ok(())
+            });
+        }
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        cmd.creation_flags(CREATE_NEW_PROCESS_GROUP);
+    }
+    cmd
+}
+
+/// Terminate `child` together with the entire worker subtree it leads.
+///
+/// On Unix a negative PID signals the whole process group created by
+/// [`new_process_group`]; on Windows `taskkill /T` walks the child tree.  The
+/// final `kill`/`wait` reaps the group leader itself.
+fn kill_process_tree(child: &mut Child) {
+    let pid = child.id();
+    #[cfg(unix)]
+    {
+        // SAFETY: sending SIGTERM to the process group is a plain syscall.
+        unsafe {
+            libc::kill(-(pid as i32), libc::SIGTERM);
+        }
+    }
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .output();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
 fn spawn_engine(engine_dir: &std::path::Path) -> Result<Child, String> {
     // Try `uv run` first (preferred), fall back to `python -m uvicorn`
     let uv_path = which_executable("uv");
 
     if let Some(uv) = uv_path {
-        Command::new(uv)
-            .args([
-                "run", "uvicorn", "src.main:app",
-                "--host", "127.0.0.1",
-                "--port", "8001",
-            ])
-            .current_dir(engine_dir)
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
+        let mut cmd = Command::new(uv);
+        cmd.args([
+            "run", "uvicorn", "src.main:app",
+            "--host", "127.0.0.1",
+            "--port", "8001",
+        ])
+        .current_dir(engine_dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+        new_process_group(&mut cmd)
             .spawn()
             .map_err(|e| format!("uv spawn error: {e}"))
     } else {
@@ -226,16 +554,17 @@ fn spawn_engine(engine_dir: &std::path::Path) -> Result<Child, String> {
             .or_else(|| which_executable("python"))
             .ok_or_else(|| "Neither uv nor python found on PATH".to_string())?;
 
-        Command::new(python)
-            .args([
-                "-m", "uvicorn", "src.main:app",
-                "--host", "127.0.0.1",
-                "--port", "8001",
-            ])
-            .current_dir(engine_dir)
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
+        let mut cmd = Command::new(python);
+        cmd.args([
+            "-m", "uvicorn", "src.main:app",
+            "--host", "127.0.0.1",
+            "--port", "8001",
+        ])
+        .current_dir(engine_dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+        new_process_group(&mut cmd)
             .spawn()
             .map_err(|e| format!("python spawn error: {e}"))
     }
@@ -245,16 +574,17 @@ fn spawn_litellm(engine_dir: &std::path::Path) -> Result<Child, String> {
     let uv_path = which_executable("uv");
 
     if let Some(uv) = uv_path {
-        Command::new(uv)
-            .args([
-                "run", "litellm",
-                "--config", "litellm_config.yaml",
-                "--port", "4000",
-            ])
-            .current_dir(engine_dir)
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
+        let mut cmd = Command::new(uv);
+        cmd.args([
+            "run", "litellm",
+            "--config", "litellm_config.yaml",
+            "--port", "4000",
+        ])
+        .current_dir(engine_dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+        new_process_group(&mut cmd)
             .spawn()
             .map_err(|e| format!("uv litellm spawn error: {e}"))
     } else {
@@ -262,16 +592,17 @@ fn spawn_litellm(engine_dir: &std::path::Path) -> Result<Child, String> {
             .or_else(|| which_executable("python"))
             .ok_or_else(|| "Neither uv nor python found on PATH".to_string())?;
 
-        Command::new(python)
-            .args([
-                "-m", "litellm",
-                "--config", "litellm_config.yaml",
-                "--port", "4000",
-            ])
-            .current_dir(engine_dir)
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
+        let mut cmd = Command::new(python);
+        cmd.args([
+            "-m", "litellm",
+            "--config", "litellm_config.yaml",
+            "--port", "4000",
+        ])
+        .current_dir(engine_dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+        new_process_group(&mut cmd)
             .spawn()
             .map_err(|e| format!("python litellm spawn error: {e}"))
     }
@@ -298,23 +629,87 @@ fn which_executable(name: &str) -> Option<String> {
 // Health polling
 // ---------------------------------------------------------------------------
 
-/// Blocking poll of both service health endpoints.
-/// Updates `BackendState.status` fields as each becomes reachable.
-fn poll_health_blocking(state: &Arc<BackendState>, max_seconds: u64) {
+/// Bounded exponential-backoff-with-jitter retry policy.
+///
+/// The classic schedule is `delay = min(max_delay, base · 2^attempt)` plus a
+/// random jitter in `[0, delay/2)` to break lockstep between callers.
+struct RetryPolicy {
+    /// Maximum number of attempts before giving up.
+    max_attempts: u32,
+    base: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Defaults tuned for slow cold-start Python venvs: up to ~8 attempts with
+    /// delays ramping from 500 ms to a 10 s ceiling.
+    fn health_default() -> Self {
+        Self {
+            max_attempts: 8,
+            base: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    /// Backoff delay for a 0-indexed `attempt`, including jitter.
+    fn delay(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base
+            .saturating_mul(1u32 << attempt.min(16))
+            .min(self.max_delay);
+        let jitter_ceiling = exp.as_millis() as u64 / 2;
+        let jitter = if jitter_ceiling == 0 {
+            0
+        } else {
+            // Cheap, dependency-free jitter source derived from the clock.
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.subsec_nanos() as u64)
+                .unwrap_or(0);
+            nanos % jitter_ceiling
+        };
+        exp + Duration::from_millis(jitter)
+    }
+}
+
+/// Blocking poll of both service health endpoints with exponential backoff.
+///
+/// On each attempt, a service whose spawned child has died (and is still
+/// unhealthy) is re-spawned, giving slow-starting Python environments several
+/// bounded retries instead of one hard timeout.  `max_seconds` is retained as
+/// an overall wall-clock ceiling for backward compatibility.
+fn poll_health_blocking(
+    app: &AppHandle,
+    state: &Arc<BackendState>,
+    engine_dir: &std::path::Path,
+    max_seconds: u64,
+) {
     let engine_url = "http://127.0.0.1:8001/health";
     let litellm_url = "http://127.0.0.1:4000/health";
-    let interval = Duration::from_millis(500);
+    let policy = RetryPolicy::health_default();
     let deadline = std::time::Instant::now() + Duration::from_secs(max_seconds);
 
     let mut engine_ok = false;
     let mut litellm_ok = false;
 
-    while std::time::Instant::now() < deadline {
+    for attempt in 0..policy.max_attempts {
+        if std::time::Instant::now() >= deadline {
+            break;
+        }
+
         if !engine_ok {
             if check_health_sync(engine_url) {
                 engine_ok = true;
                 state.status.lock().unwrap().engine_ready = true;
                 eprintln!("[Backend] cognitive-engine healthy.");
+            } else if attempt > 0
+                && (child_slot_empty(state, Service::Engine)
+                    || !child_is_alive(state, Service::Engine))
+            {
+                // Child died — or its initial spawn failed so nothing was ever
+                // tracked — re-spawn and keep polling.
+                eprintln!("[Backend] cognitive-engine not running — re-spawning.");
+                restart_service(app, state, engine_dir, Service::Engine);
             }
         }
         if !litellm_ok {
@@ -322,6 +717,12 @@ fn poll_health_blocking(state: &Arc<BackendState>, max_seconds: u64) {
                 litellm_ok = true;
                 state.status.lock().unwrap().litellm_ready = true;
                 eprintln!("[Backend] LiteLLM proxy healthy.");
+            } else if attempt > 0
+                && (child_slot_empty(state, Service::Litellm)
+                    || !child_is_alive(state, Service::Litellm))
+            {
+                eprintln!("[Backend] LiteLLM not running — re-spawning.");
+                restart_service(app, state, engine_dir, Service::Litellm);
             }
         }
 
@@ -330,10 +731,17 @@ fn poll_health_blocking(state: &Arc<BackendState>, max_seconds: u64) {
             return;
         }
 
-        std::thread::sleep(interval);
+        let delay = policy.delay(attempt);
+        eprintln!(
+            "[Backend] Services not ready (attempt {}/{}) — retrying in {:?}.",
+            attempt + 1,
+            policy.max_attempts,
+            delay
+        );
+        std::thread::sleep(delay);
     }
 
-    // Timeout — mark error for whichever didn't respond
+    // Exhausted attempts / deadline — mark error for whichever didn't respond.
     let mut s = state.status.lock().unwrap();
     if !engine_ok && !litellm_ok {
         s.error = Some("Both cognitive-engine and LiteLLM failed to start within timeout.".into());
@@ -396,17 +804,15 @@ fn check_health_sync(url: &str) -> bool {
 pub fn shutdown_services(state: &Arc<BackendState>) {
     if let Ok(mut guard) = state.engine_process.lock() {
         if let Some(ref mut child) = *guard {
-            eprintln!("[Backend] Stopping cognitive-engine (pid {})…", child.id());
-            let _ = child.kill();
-            let _ = child.wait();
+            eprintln!("[Backend] Stopping cognitive-engine tree (pid {})…", child.id());
+            kill_process_tree(child);
         }
         *guard = None;
     }
     if let Ok(mut guard) = state.litellm_process.lock() {
         if let Some(ref mut child) = *guard {
-            eprintln!("[Backend] Stopping LiteLLM proxy (pid {})…", child.id());
-            let _ = child.kill();
-            let _ = child.wait();
+            eprintln!("[Backend] Stopping LiteLLM proxy tree (pid {})…", child.id());
+            kill_process_tree(child);
         }
         *guard = None;
     }
@@ -28,9 +28,139 @@ pub struct VocoApiKeys {
     pub google_api_key: String,
 }
 
+/// Argon2id parameters recorded in the vault header so a file can always be
+/// decrypted with the settings it was written under, even if the defaults
+/// change in a later release.
+#[derive(Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB.
+    pub memory_kib: u32,
+    /// Number of iterations (time cost).
+    pub iterations: u32,
+    /// Degree of parallelism (lanes).
+    pub parallelism: u32,
+    /// 16-byte random salt, base64-encoded.
+    pub salt: String,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // Sensible interactive defaults (~19 MiB, 2 passes) — tunable per file.
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+            salt: String::new(),
+        }
+    }
+}
+
+/// Versioned encrypted envelope wrapping the serialized [`VocoApiKeys`] JSON.
+///
+/// The ciphertext is XChaCha20-Poly1305 over the plaintext config with a
+/// random 24-byte nonce; the key is derived from the user's passphrase via
+/// Argon2id using the recorded [`KdfParams`].
+#[derive(Serialize, Deserialize)]
+pub struct VaultEnvelope {
+    pub version: u32,
+    pub kdf: KdfParams,
+    /// 24-byte random nonce, base64-encoded.
+    pub nonce: String,
+    /// AEAD ciphertext (includes the Poly1305 tag), base64-encoded.
+    pub ciphertext: String,
+}
+
+/// Derive a 32-byte key from `passphrase` using Argon2id with `params`.
+fn derive_vault_key(passphrase: &str, params: &KdfParams) -> Result<[u8; 32], String> {
+    use argon2::{Algorithm, Argon2, Params, Version};
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let salt = STANDARD
+        .decode(&params.salt)
+        .map_err(|e| format!("Invalid KDF salt: {e}"))?;
+    let argon_params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(32),
+    )
+    .map_err(|e| format!("Invalid Argon2 params: {e}"))?;
+    let argon = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon_params);
+
+    let mut key = [0u8; 32];
+    argon
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {e}"))?;
+    Ok(key)
+}
+
+/// Serialize and encrypt `keys` under `passphrase` into a [`VaultEnvelope`].
+fn encrypt_vault(keys: &VocoApiKeys, passphrase: &str) -> Result<VaultEnvelope, String> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+    use chacha20poly1305::{AeadCore, XChaCha20Poly1305};
+
+    let mut salt = [0u8; 16];
+    chacha20poly1305::aead::rand_core::RngCore::fill_bytes(&mut OsRng, &mut salt);
+    let kdf = KdfParams {
+        salt: STANDARD.encode(salt),
+        ..KdfParams::default()
+    };
+
+    let key = derive_vault_key(passphrase, &kdf)?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key)
+        .map_err(|e| format!("Cipher init failed: {e}"))?;
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let plaintext = serde_json::to_vec(keys).map_err(|e| format!("Serialization error: {e}"))?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_ref())
+        .map_err(|e| format!("Encryption failed: {e}"))?;
+
+    Ok(VaultEnvelope {
+        version: 1,
+        kdf,
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+/// Decrypt a [`VaultEnvelope`] back into [`VocoApiKeys`] using `passphrase`.
+fn decrypt_vault(envelope: &VaultEnvelope, passphrase: &str) -> Result<VocoApiKeys, String> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use chacha20poly1305::aead::{Aead, KeyInit};
+    use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+    let key = derive_vault_key(passphrase, &envelope.kdf)?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key)
+        .map_err(|e| format!("Cipher init failed: {e}"))?;
+
+    let nonce_bytes = STANDARD
+        .decode(&envelope.nonce)
+        .map_err(|e| format!("Invalid nonce: {e}"))?;
+    let ciphertext = STANDARD
+        .decode(&envelope.ciphertext)
+        .map_err(|e| format!("Invalid ciphertext: {e}"))?;
+
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| "Decryption failed — wrong passphrase or corrupted vault".to_string())?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| format!("Parse error: {e}"))
+}
+
 /// Persist API keys to `{app_config_dir}/config.json`.
+///
+/// When a `passphrase` is supplied the keys are written as an encrypted
+/// [`VaultEnvelope`] (Argon2id + XChaCha20-Poly1305); otherwise they are
+/// stored as plaintext JSON for backward compatibility.  A plaintext file is
+/// transparently re-encrypted the first time it is saved with a passphrase.
 #[tauri::command]
-pub async fn save_api_keys(app: AppHandle, keys: VocoApiKeys) -> Result<(), String> {
+pub async fn save_api_keys(
+    app: AppHandle,
+    keys: VocoApiKeys,
+    passphrase: Option<String>,
+) -> Result<(), String> {
     use tauri::Manager;
     let config_dir = app
         .path()
@@ -38,8 +168,16 @@ pub async fn save_api_keys(app: AppHandle, keys: VocoApiKeys) -> Result<(), Stri
         .map_err(|e| format!("Cannot resolve app config dir: {e}"))?;
     std::fs::create_dir_all(&config_dir)
         .map_err(|e| format!("Cannot create config dir: {e}"))?;
-    let json = serde_json::to_string_pretty(&keys)
-        .map_err(|e| format!("Serialization error: {e}"))?;
+
+    let json = match passphrase {
+        Some(ref pass) if !pass.is_empty() => {
+            let envelope = encrypt_vault(&keys, pass)?;
+            serde_json::to_string_pretty(&envelope)
+                .map_err(|e| format!("Serialization error: {e}"))?
+        }
+        _ => serde_json::to_string_pretty(&keys)
+            .map_err(|e| format!("Serialization error: {e}"))?,
+    };
     std::fs::write(config_dir.join("config.json"), json)
         .map_err(|e| format!("Write error: {e}"))?;
     Ok(())
@@ -47,8 +185,14 @@ pub async fn save_api_keys(app: AppHandle, keys: VocoApiKeys) -> Result<(), Stri
 
 /// Load API keys from `{app_config_dir}/config.json`.
 /// Returns defaults (empty strings) if the file doesn't exist yet.
+///
+/// If the file is an encrypted [`VaultEnvelope`] the `passphrase` is required
+/// to derive the key and decrypt it; plaintext files are read directly.
 #[tauri::command]
-pub async fn load_api_keys(app: AppHandle) -> Result<VocoApiKeys, String> {
+pub async fn load_api_keys(
+    app: AppHandle,
+    passphrase: Option<String>,
+) -> Result<VocoApiKeys, String> {
     use tauri::Manager;
     let config_path = app
         .path()
@@ -60,6 +204,15 @@ pub async fn load_api_keys(app: AppHandle) -> Result<VocoApiKeys, String> {
     }
     let raw = std::fs::read_to_string(&config_path)
         .map_err(|e| format!("Read error: {e}"))?;
+
+    // Detect the encrypted envelope by its shape; fall back to plaintext.
+    if let Ok(envelope) = serde_json::from_str::<VaultEnvelope>(&raw) {
+        let pass = passphrase
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| "Vault is encrypted — a passphrase is required".to_string())?;
+        return decrypt_vault(&envelope, &pass);
+    }
+
     serde_json::from_str(&raw).map_err(|e| format!("Parse error: {e}"))
 }
 
@@ -74,6 +227,92 @@ pub struct IdeSyncResult {
     pub success: bool,
     pub message: String,
     pub path: String,
+    /// The proposed merged config (pretty JSON), populated whenever a merge was
+    /// built so the frontend can show a diff; `None` when the IDE was skipped
+    /// or the merge failed. Only written to disk when `confirm` is true.
+    pub proposed: Option<String>,
+}
+
+/// How the MCP server is reached. Mirrors the two transports MCP clients
+/// support: a network endpoint (SSE/streamable HTTP) or a spawned stdio child.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TransportDescriptor {
+    /// SSE / streamable-HTTP endpoint.
+    Sse { url: String },
+    /// Stdio launch config.
+    Stdio {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+impl Default for TransportDescriptor {
+    fn default() -> Self {
+        // Backward-compatible default: the cognitive engine's SSE endpoint.
+        TransportDescriptor::Sse {
+            url: "http://localhost:8001/mcp".to_string(),
+        }
+    }
+}
+
+/// The server-entry schema an editor expects under its servers key. Editors
+/// disagree on both the field names and which transports they accept, so the
+/// entry body is built per schema rather than shared.
+#[derive(Clone, Copy)]
+enum IdeSchema {
+    /// Cursor / Windsurf: a bare `{ "url" }` for SSE or `{ "command", "args" }`
+    /// for stdio, no discriminant field.
+    Plain,
+    /// Claude Desktop: stdio only, `{ "command", "args" }`. SSE is rejected.
+    StdioOnly,
+    /// VS Code: requires an explicit `"type": "sse" | "stdio"` discriminant.
+    Tagged,
+    /// Zed: `{ "source": "custom", "command": { "path", "args" } }`, stdio only.
+    Zed,
+}
+
+impl TransportDescriptor {
+    /// Build the JSON server-entry body for this transport in the shape the
+    /// given editor expects, or `Err` if the editor can't use this transport.
+    fn entry(&self, schema: IdeSchema) -> Result<serde_json::Value, String> {
+        match (schema, self) {
+            (IdeSchema::Plain, TransportDescriptor::Sse { url }) => {
+                Ok(serde_json::json!({ "url": url }))
+            }
+            (IdeSchema::Plain, TransportDescriptor::Stdio { command, args }) => {
+                Ok(serde_json::json!({ "command": command, "args": args }))
+            }
+            (IdeSchema::StdioOnly, TransportDescriptor::Stdio { command, args }) => {
+                Ok(serde_json::json!({ "command": command, "args": args }))
+            }
+            (IdeSchema::Tagged, TransportDescriptor::Sse { url }) => {
+                Ok(serde_json::json!({ "type": "sse", "url": url }))
+            }
+            (IdeSchema::Tagged, TransportDescriptor::Stdio { command, args }) => {
+                Ok(serde_json::json!({ "type": "stdio", "command": command, "args": args }))
+            }
+            (IdeSchema::Zed, TransportDescriptor::Stdio { command, args }) => Ok(serde_json::json!({
+                "source": "custom",
+                "command": { "path": command, "args": args }
+            })),
+            // Stdio-only editors can't front an SSE endpoint.
+            (IdeSchema::StdioOnly | IdeSchema::Zed, TransportDescriptor::Sse { .. }) => {
+                Err("only supports a stdio transport; re-sync with a stdio command".to_string())
+            }
+        }
+    }
+}
+
+/// A sync target: the IDE's display name, its config file, the JSON key under
+/// which it nests MCP servers, and the entry schema it expects (all differ
+/// across editors).
+struct IdeTarget {
+    name: &'static str,
+    path: PathBuf,
+    servers_key: &'static str,
+    schema: IdeSchema,
 }
 
 /// Resolve the user's home directory without an external crate.
@@ -82,37 +321,98 @@ fn home_dir() -> Option<PathBuf> {
     std::env::var(key).ok().map(PathBuf::from)
 }
 
-/// Inject a `voco-local` MCP server entry into Cursor and Windsurf config files.
+/// Per-OS config path for Claude Desktop's `claude_desktop_config.json`.
+fn claude_desktop_config(home: &std::path::Path) -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        home.join("Library")
+            .join("Application Support")
+            .join("Claude")
+            .join("claude_desktop_config.json")
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // %APPDATA%\Claude\claude_desktop_config.json
+        std::env::var("APPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| home.join("AppData").join("Roaming"))
+            .join("Claude")
+            .join("claude_desktop_config.json")
+    }
+    #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+    {
+        home.join(".config")
+            .join("Claude")
+            .join("claude_desktop_config.json")
+    }
+}
+
+/// Inject (or update) a `voco-local` MCP server entry across the supported
+/// editors.
+///
+/// Supports both SSE/HTTP and stdio transports via [`TransportDescriptor`],
+/// and targets Cursor, Windsurf, Claude Desktop, VS Code, and Zed — each with
+/// its own config path and server-key schema.  Existing `voco-local`
+/// customizations are preserved (only the transport fields are overwritten)
+/// rather than clobbered.
 ///
-/// - Reads the existing `mcp.json` (or starts with `{}`).
-/// - Merges `mcpServers.voco-local` pointing to the cognitive engine's MCP SSE endpoint.
-/// - Writes back, creating the file if it didn't exist.
-/// - Skips any IDE whose config directory doesn't exist (i.e. not installed).
+/// With `confirm = false` (the default) the command runs as a dry-run: it
+/// returns the proposed merge for each IDE without touching disk.  The caller
+/// reviews the diff and re-invokes with `confirm = true` to write.
 #[tauri::command]
-pub async fn sync_ide_config() -> Result<Vec<IdeSyncResult>, String> {
+pub async fn sync_ide_config(
+    transport: Option<TransportDescriptor>,
+    confirm: Option<bool>,
+) -> Result<Vec<IdeSyncResult>, String> {
     let home = home_dir().ok_or_else(|| "Cannot determine home directory".to_string())?;
-
-    // SSE transport — cognitive engine will expose this endpoint.
-    let voco_entry = serde_json::json!({
-        "url": "http://localhost:8001/mcp"
-    });
-
-    let targets: Vec<(&str, PathBuf)> = vec![
-        ("Cursor", home.join(".cursor").join("mcp.json")),
-        ("Windsurf", home.join(".windsurf").join("mcp.json")),
+    let transport = transport.unwrap_or_default();
+    let confirm = confirm.unwrap_or(false);
+
+    let targets = vec![
+        IdeTarget {
+            name: "Cursor",
+            path: home.join(".cursor").join("mcp.json"),
+            servers_key: "mcpServers",
+            schema: IdeSchema::Plain,
+        },
+        IdeTarget {
+            name: "Windsurf",
+            path: home.join(".windsurf").join("mcp.json"),
+            servers_key: "mcpServers",
+            schema: IdeSchema::Plain,
+        },
+        IdeTarget {
+            name: "Claude Desktop",
+            path: claude_desktop_config(&home),
+            servers_key: "mcpServers",
+            schema: IdeSchema::StdioOnly,
+        },
+        IdeTarget {
+            name: "VS Code",
+            path: home.join(".vscode").join("mcp.json"),
+            servers_key: "servers",
+            schema: IdeSchema::Tagged,
+        },
+        IdeTarget {
+            name: "Zed",
+            path: home.join(".config").join("zed").join("settings.json"),
+            servers_key: "context_servers",
+            schema: IdeSchema::Zed,
+        },
     ];
 
     let mut results = Vec::new();
 
-    for (ide_name, config_path) in targets {
-        let dir = match config_path.parent() {
+    for target in targets {
+        let dir = match target.path.parent() {
             Some(d) => d.to_owned(),
             None => {
                 results.push(IdeSyncResult {
-                    ide: ide_name.to_string(),
+                    ide: target.name.to_string(),
                     success: false,
                     message: "Invalid config path".to_string(),
-                    path: config_path.display().to_string(),
+                    path: target.path.display().to_string(),
+                    proposed: None,
                 });
                 continue;
             }
@@ -121,17 +421,35 @@ pub async fn sync_ide_config() -> Result<Vec<IdeSyncResult>, String> {
         // If the IDE config directory doesn't exist, the IDE isn't installed.
         if !dir.exists() {
             results.push(IdeSyncResult {
-                ide: ide_name.to_string(),
+                ide: target.name.to_string(),
                 success: false,
-                message: format!("{ide_name} not found — config directory does not exist."),
-                path: config_path.display().to_string(),
+                message: format!("{} not found — config directory does not exist.", target.name),
+                path: target.path.display().to_string(),
+                proposed: None,
             });
             continue;
         }
 
+        // Build the entry in this editor's schema. Bail early if the editor
+        // can't use the requested transport rather than writing a config it
+        // won't accept.
+        let voco_entry = match transport.entry(target.schema) {
+            Ok(entry) => entry,
+            Err(why) => {
+                results.push(IdeSyncResult {
+                    ide: target.name.to_string(),
+                    success: false,
+                    message: format!("{} {}", target.name, why),
+                    path: target.path.display().to_string(),
+                    proposed: None,
+                });
+                continue;
+            }
+        };
+
         // Read existing config or start fresh.
-        let mut config: serde_json::Value = if config_path.exists() {
-            let raw = std::fs::read_to_string(&config_path).unwrap_or_default();
+        let mut config: serde_json::Value = if target.path.exists() {
+            let raw = std::fs::read_to_string(&target.path).unwrap_or_default();
             serde_json::from_str(&raw).unwrap_or(serde_json::json!({}))
         } else {
             serde_json::json!({})
@@ -141,36 +459,73 @@ pub async fn sync_ide_config() -> Result<Vec<IdeSyncResult>, String> {
             config = serde_json::json!({});
         }
 
-        // Merge voco-local into mcpServers.
+        // Merge voco-local under the IDE's server key, preserving any existing
+        // customizations on the voco-local entry (only transport fields change).
         let map = config.as_object_mut().unwrap();
         let servers = map
-            .entry("mcpServers")
+            .entry(target.servers_key)
             .or_insert_with(|| serde_json::json!({}));
 
         if let Some(servers_map) = servers.as_object_mut() {
-            servers_map.insert("voco-local".to_string(), voco_entry.clone());
-        }
-
-        match serde_json::to_string_pretty(&config) {
-            Ok(json_str) => match std::fs::write(&config_path, json_str) {
-                Ok(()) => results.push(IdeSyncResult {
-                    ide: ide_name.to_string(),
-                    success: true,
-                    message: format!("voco-local synced to {ide_name}"),
-                    path: config_path.display().to_string(),
-                }),
-                Err(e) => results.push(IdeSyncResult {
-                    ide: ide_name.to_string(),
+            let mut merged = servers_map
+                .remove("voco-local")
+                .and_then(|v| v.as_object().cloned())
+                .unwrap_or_default();
+            // Drop any transport fields from the previous entry before merging
+            // the new one — the two transports are mutually exclusive, so
+            // switching (e.g. SSE → stdio) must not leave `url` alongside
+            // `command`/`args`. Genuinely user-added keys are preserved.
+            const TRANSPORT_KEYS: [&str; 5] = ["url", "command", "args", "type", "source"];
+            for key in TRANSPORT_KEYS {
+                merged.remove(key);
+            }
+            if let Some(entry_obj) = voco_entry.as_object() {
+                for (k, v) in entry_obj {
+                    merged.insert(k.clone(), v.clone());
+                }
+            }
+            servers_map.insert("voco-local".to_string(), serde_json::Value::Object(merged));
+        }
+
+        let proposed = match serde_json::to_string_pretty(&config) {
+            Ok(s) => s,
+            Err(e) => {
+                results.push(IdeSyncResult {
+                    ide: target.name.to_string(),
                     success: false,
-                    message: format!("Write failed: {e}"),
-                    path: config_path.display().to_string(),
-                }),
-            },
+                    message: format!("Serialization failed: {e}"),
+                    path: target.path.display().to_string(),
+                    proposed: None,
+                });
+                continue;
+            }
+        };
+
+        if !confirm {
+            results.push(IdeSyncResult {
+                ide: target.name.to_string(),
+                success: true,
+                message: format!("Dry-run — review proposed merge for {}", target.name),
+                path: target.path.display().to_string(),
+                proposed: Some(proposed),
+            });
+            continue;
+        }
+
+        match std::fs::write(&target.path, &proposed) {
+            Ok(()) => results.push(IdeSyncResult {
+                ide: target.name.to_string(),
+                success: true,
+                message: format!("voco-local synced to {}", target.name),
+                path: target.path.display().to_string(),
+                proposed: Some(proposed),
+            }),
             Err(e) => results.push(IdeSyncResult {
-                ide: ide_name.to_string(),
+                ide: target.name.to_string(),
                 success: false,
-                message: format!("Serialization failed: {e}"),
-                path: config_path.display().to_string(),
+                message: format!("Write failed: {e}"),
+                path: target.path.display().to_string(),
+                proposed: Some(proposed),
             }),
         }
     }
@@ -340,9 +695,12 @@ pub async fn open_url(url: String) -> Result<(), String> {
 
 /// Execute a shell command within an authorized project directory.
 ///
-/// # Security — Double-Lock
+/// # Security — Triple-Lock
 /// - `project_path` must be absolute and canonicalizable.
 /// - Command runs with `current_dir` locked to the project path.
+/// - The command's leading executable must be allowed by the project's
+///   [`CommandPolicy`]; anything not yet allowed is rejected, or (when the
+///   policy opts into approve-on-first-use) surfaced to the user and recorded.
 /// - Selects `cmd /C` on Windows, `sh -c` on Unix.
 #[tauri::command]
 pub async fn execute_command(
@@ -350,6 +708,8 @@ pub async fn execute_command(
     command: String,
     project_path: PathBuf,
 ) -> Result<String, String> {
+    use crate::policy::{leading_executable, CommandPolicy};
+
     if !project_path.is_absolute() {
         return Err(format!(
             "project_path must be absolute: '{}'",
@@ -361,6 +721,37 @@ pub async fn execute_command(
         .canonicalize()
         .map_err(|e| format!("Cannot canonicalize project_path: {e}"))?;
 
+    // --- Capability check: is this command's executable allowed here? ---
+    let executable = leading_executable(&command)
+        .ok_or_else(|| "Empty command — nothing to execute".to_string())?;
+    let mut policy = CommandPolicy::load(&canonical_path);
+    if !policy.permits(&executable) {
+        if !policy.approve_on_first_use {
+            return Err(format!(
+                "Command '{executable}' is not in this project's allowlist and \
+                 approve-on-first-use is disabled."
+            ));
+        }
+        // Surface an approval prompt; only a positive response allows + records.
+        let approved = {
+            use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+            app.dialog()
+                .message(format!(
+                    "Allow Voco to run '{executable}' in this project?\n\nFull command:\n{command}"
+                ))
+                .title("Command approval")
+                .buttons(MessageDialogButtons::OkCancelCustom(
+                    "Allow".into(),
+                    "Deny".into(),
+                ))
+                .blocking_show()
+        };
+        if !approved {
+            return Err(format!("User denied execution of '{executable}'."));
+        }
+        policy.allow(&executable, &canonical_path)?;
+    }
+
     let path_str = canonical_path
         .to_str()
         .ok_or_else(|| "Invalid project path encoding".to_string())?;
@@ -405,7 +796,9 @@ pub async fn execute_command(
 ///
 /// # Security
 /// - Both `file_path` and `project_root` must be absolute.
-/// - The canonical parent of `file_path` must start with the canonical `project_root`.
+/// - Validated through [`AccessScope`]: the canonical parent of `file_path`
+///   must land inside `project_root`, and a symlinked final component cannot
+///   redirect the write outside the root.
 /// - Parent directories are created automatically.
 #[tauri::command]
 pub async fn write_file(
@@ -413,47 +806,26 @@ pub async fn write_file(
     content: String,
     project_root: PathBuf,
 ) -> Result<String, String> {
-    if !file_path.is_absolute() {
-        return Err(format!(
-            "file_path must be absolute: '{}'",
-            file_path.display()
-        ));
-    }
-    if !project_root.is_absolute() {
-        return Err(format!(
-            "project_root must be absolute: '{}'",
-            project_root.display()
-        ));
-    }
+    use crate::scope::{AccessOp, AccessScope};
 
-    let canonical_root = project_root
-        .canonicalize()
-        .map_err(|e| format!("Cannot canonicalize project_root: {e}"))?;
-
-    // Ensure parent directory exists before canonicalizing the file path
+    // Create the parent tree first so the scope can canonicalize it.
     let parent = file_path
         .parent()
         .ok_or_else(|| "file_path has no parent directory".to_string())?;
-
     std::fs::create_dir_all(parent)
         .map_err(|e| format!("Failed to create parent directories: {e}"))?;
 
-    let canonical_parent = parent
-        .canonicalize()
-        .map_err(|e| format!("Cannot canonicalize file parent: {e}"))?;
+    let scope = AccessScope::project_root(&project_root)?;
+    let canonical_file = scope.validate_new(&file_path, AccessOp::Write)?;
 
-    if !canonical_parent.starts_with(&canonical_root) {
-        return Err(format!(
-            "Security Violation: file_path '{}' is outside project_root '{}'",
-            file_path.display(),
-            project_root.display()
-        ));
-    }
-
-    std::fs::write(&file_path, &content)
+    std::fs::write(&canonical_file, &content)
         .map_err(|e| format!("Failed to write file: {e}"))?;
 
-    Ok(format!("Written {} bytes to {}", content.len(), file_path.display()))
+    Ok(format!(
+        "Written {} bytes to {}",
+        content.len(),
+        canonical_file.display()
+    ))
 }
 
 // ---------------------------------------------------------------------------
@@ -469,7 +841,7 @@ pub async fn write_file(
 ///
 /// Returns a JSON string that Python passes to Claude for threat analysis.
 #[tauri::command]
-pub async fn scan_security(project_path: PathBuf) -> Result<String, String> {
+pub async fn scan_security(app: AppHandle, project_path: PathBuf) -> Result<String, String> {
     if !project_path.is_absolute() {
         return Err(format!(
             "project_path must be absolute: '{}'",
@@ -525,24 +897,139 @@ pub async fn scan_security(project_path: PathBuf) -> Result<String, String> {
 
     let env_issues = scan_env_files_for_secrets(&project_path, patterns);
 
+    // --- 3. Scan committed git history for leaked secrets (best-effort) ---
+    let history_issues = scan_git_history_for_secrets(&app, &project_path, patterns).await;
+
     let report = serde_json::json!({
         "project_path": project_path.display().to_string(),
         "dependencies": dependencies,
         "env_issues": env_issues,
+        "history_issues": history_issues,
         "scan_timestamp": "local"
     });
 
     serde_json::to_string(&report).map_err(|e| format!("Serialization error: {e}"))
 }
 
+/// Shannon entropy (`H = -Σ p_i·log2(p_i)`) of a string's character frequencies.
+///
+/// Returns bits per character; higher values indicate random-looking data such
+/// as base64/hex credentials rather than human-readable placeholders.
+fn shannon_entropy(value: &str) -> f64 {
+    if value.is_empty() {
+        return 0.0;
+    }
+    let mut counts: std::collections::HashMap<char, usize> = std::collections::HashMap::new();
+    for c in value.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    let len = value.chars().count() as f64;
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// True if `value` looks like hex, either case (so the lower hex entropy ceiling applies).
+fn looks_like_hex(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// A single secret detection within one line, independent of where the line
+/// came from (working-tree env file or a git diff hunk).
+struct Detection {
+    pattern_matched: String,
+    match_offset: usize,
+    issue_type: String,
+    severity: String,
+    /// Present only for entropy-based detections.
+    entropy: Option<f64>,
+}
+
+// Entropy thresholds: mixed-case/base64-like strings carry more bits per char
+// than hex, so hold hex to a lower bar to avoid missing long keys.
+const ENTROPY_THRESHOLD_MIXED: f64 = 4.0;
+const ENTROPY_THRESHOLD_HEX: f64 = 3.0;
+const MIN_ENTROPY_LEN: usize = 20;
+
+/// Run the Aho-Corasick prefix detectors and generic entropy detector over a
+/// single `KEY=value` line. `is_example` downgrades known-prefix severities.
+fn detect_line_secrets(
+    line: &str,
+    automaton: &aho_corasick::AhoCorasick,
+    patterns: &[(&str, &str, &str)],
+    is_example: bool,
+) -> Vec<Detection> {
+    let mut out = Vec::new();
+
+    let mut matched_prefix = false;
+    for m in automaton.find_iter(line) {
+        matched_prefix = true;
+        let (pattern, description, severity) = patterns[m.pattern()];
+        out.push(Detection {
+            pattern_matched: pattern.to_string(),
+            match_offset: m.start(),
+            issue_type: description.to_string(),
+            severity: if is_example { "low" } else { severity }.to_string(),
+            entropy: None,
+        });
+    }
+
+    if matched_prefix {
+        return out;
+    }
+
+    // Generic high-entropy detection for values with no known prefix.
+    let value_part = line.splitn(2, '=').nth(1).unwrap_or("").trim();
+    if value_part.len() >= MIN_ENTROPY_LEN {
+        let entropy = shannon_entropy(value_part);
+        let threshold = if looks_like_hex(value_part) {
+            ENTROPY_THRESHOLD_HEX
+        } else {
+            ENTROPY_THRESHOLD_MIXED
+        };
+        if entropy > threshold {
+            out.push(Detection {
+                pattern_matched: "<high-entropy>".to_string(),
+                match_offset: line.splitn(2, '=').next().map(|k| k.len() + 1).unwrap_or(0),
+                issue_type: "High-entropy value (possible secret with no known prefix)".to_string(),
+                severity: if is_example { "low" } else { "medium" }.to_string(),
+                entropy: Some(entropy),
+            });
+        }
+    }
+
+    out
+}
+
 /// Walk the project root (and common service subdirs) for `.env*` files,
 /// check each non-comment line for known secret prefixes.
+///
+/// Prefix matching runs through a single Aho-Corasick automaton built once
+/// from all patterns (O(lines) instead of O(lines × patterns)), and values
+/// that match no known prefix are additionally flagged by Shannon-entropy.
 fn scan_env_files_for_secrets(
     project_path: &PathBuf,
     patterns: &[(&str, &str, &str)],
 ) -> Vec<serde_json::Value> {
+    use aho_corasick::{AhoCorasick, MatchKind};
+
     let mut issues = Vec::new();
 
+    // Build the automaton once; pattern index maps back into `patterns`.
+    // LeftmostLongest so the most specific prefix wins (e.g. `sk-proj-` over
+    // `sk-`) and every line is classified by its strongest match.
+    let automaton = match AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(patterns.iter().map(|(p, _, _)| *p))
+    {
+        Ok(a) => a,
+        Err(_) => return issues,
+    };
+
     // Directories to check — project root + common monorepo service dirs
     let search_dirs: Vec<PathBuf> = {
         let mut dirs = vec![project_path.clone()];
@@ -606,24 +1093,27 @@ fn scan_env_files_for_secrets(
                     continue;
                 }
 
-                for (pattern, description, severity) in patterns {
-                    if line.contains(pattern) {
-                        let key = line.splitn(2, '=').next().unwrap_or("").trim().to_string();
-                        issues.push(serde_json::json!({
-                            "file": rel_path,
-                            "line": line_num + 1,
-                            "key": key,
-                            "severity": if is_example { "low" } else { severity },
-                            "pattern_matched": pattern,
-                            "issue_type": description,
-                            "note": if is_example {
-                                "Example/template file — verify this is not a real secret"
-                            } else {
-                                "Potentially real secret detected in env file"
-                            }
-                        }));
-                        break; // one issue per line
+                let key = line.splitn(2, '=').next().unwrap_or("").trim().to_string();
+
+                for det in detect_line_secrets(line, &automaton, patterns, is_example) {
+                    let mut issue = serde_json::json!({
+                        "file": rel_path,
+                        "line": line_num + 1,
+                        "key": key,
+                        "severity": det.severity,
+                        "pattern_matched": det.pattern_matched,
+                        "match_offset": det.match_offset,
+                        "issue_type": det.issue_type,
+                        "note": if is_example {
+                            "Example/template file — verify this is not a real secret"
+                        } else {
+                            "Potentially real secret detected in env file"
+                        }
+                    });
+                    if let Some(entropy) = det.entropy {
+                        issue["entropy"] = serde_json::json!(entropy);
                     }
+                    issues.push(issue);
                 }
             }
         }
@@ -632,6 +1122,130 @@ fn scan_env_files_for_secrets(
     issues
 }
 
+/// Scan committed git history for secrets that were added at some point and
+/// may still be recoverable even if gone from HEAD.
+///
+/// When `project_path` is a git repository this shells out to
+/// `git log -p --all` (via the same shell bridge used elsewhere) and runs the
+/// Aho-Corasick/entropy detectors over every added (`+`) line in each diff
+/// hunk, attributing findings to the commit SHA, author, and date. Returns an
+/// empty list when the project is not a repo or `git` is unavailable.
+async fn scan_git_history_for_secrets(
+    app: &AppHandle,
+    project_path: &PathBuf,
+    patterns: &[(&str, &str, &str)],
+) -> Vec<serde_json::Value> {
+    use aho_corasick::{AhoCorasick, MatchKind};
+
+    let mut issues = Vec::new();
+
+    if !project_path.join(".git").exists() {
+        return issues;
+    }
+    let path_str = match project_path.to_str() {
+        Some(p) => p,
+        None => return issues,
+    };
+
+    // LeftmostLongest so the most specific prefix wins (e.g. `sk-proj-` over
+    // `sk-`) and every line is classified by its strongest match.
+    let automaton = match AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(patterns.iter().map(|(p, _, _)| *p))
+    {
+        Ok(a) => a,
+        Err(_) => return issues,
+    };
+
+    // Commit header lines are tagged so we can re-attach added lines to the
+    // commit they belong to; \x1f separates SHA / author / ISO-8601 date.
+    let output = match app
+        .shell()
+        .command("git")
+        .args([
+            "-C",
+            path_str,
+            "log",
+            "-p",
+            "--all",
+            "--no-color",
+            "--pretty=format:VOCO\x1f%H\x1f%an\x1f%aI",
+        ])
+        .output()
+        .await
+    {
+        Ok(o) => o,
+        Err(_) => return issues,
+    };
+
+    if !output.status.success() {
+        return issues;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let (mut sha, mut author, mut date) = (String::new(), String::new(), String::new());
+    let mut current_file = String::new();
+
+    for line in text.lines() {
+        if let Some(header) = line.strip_prefix("VOCO\x1f") {
+            let mut parts = header.splitn(3, '\x1f');
+            sha = parts.next().unwrap_or("").to_string();
+            author = parts.next().unwrap_or("").to_string();
+            date = parts.next().unwrap_or("").to_string();
+            continue;
+        }
+        if let Some(file) = line.strip_prefix("+++ b/") {
+            current_file = file.to_string();
+            continue;
+        }
+        // Added lines start with a single '+' (skip the '+++' file header).
+        let added = match line.strip_prefix('+') {
+            Some(rest) if !line.starts_with("+++") => rest,
+            _ => continue,
+        };
+        let is_example = current_file.contains("example")
+            || current_file.contains("sample")
+            || current_file.contains("template");
+        for det in detect_line_secrets(added, &automaton, patterns, is_example) {
+            let key = added.splitn(2, '=').next().unwrap_or("").trim().to_string();
+            let mut issue = serde_json::json!({
+                "commit": sha,
+                "author": author,
+                "date": date,
+                "file": current_file,
+                "key": key,
+                "severity": det.severity,
+                "pattern_matched": det.pattern_matched,
+                "match_offset": det.match_offset,
+                "issue_type": det.issue_type,
+                "note": "Secret found in git history — rotate this credential even if removed from HEAD"
+            });
+            if let Some(entropy) = det.entropy {
+                issue["entropy"] = serde_json::json!(entropy);
+            }
+            issues.push(issue);
+        }
+    }
+
+    issues
+}
+
+/// A byte-offset span of a submatch within a matched line.
+#[derive(Serialize)]
+pub struct SubMatch {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A single structured search hit parsed from ripgrep's `--json` output.
+#[derive(Serialize)]
+pub struct SearchHit {
+    pub path: String,
+    pub line_number: u64,
+    pub line: String,
+    pub submatches: Vec<SubMatch>,
+}
+
 /// Search a project directory using the bundled ripgrep sidecar.
 ///
 /// # Security
@@ -646,29 +1260,32 @@ pub async fn search_project(
     max_count: Option<u32>,
     file_glob: Option<String>,
     context_lines: Option<u32>,
+    output: Option<String>,
 ) -> Result<String, String> {
-    // Validate path: accept both Windows (C:\...) and Unix-style (/...) absolute paths
-    let path_str = project_path.to_string_lossy();
-    let is_absolute = project_path.is_absolute() || path_str.starts_with('/');
-    
-    if !is_absolute {
-        return Err(format!(
-            "Project path must be absolute (Windows C:\\... or Unix /...): '{}'",
-            project_path.display()
-        ));
-    }
-
-    let path_str = project_path
+    // Resolve + validate through the central ACL (canonicalizes, resolves
+    // symlinks, rejects paths escaping the project root).
+    use crate::scope::{AccessOp, AccessScope};
+    let scope = AccessScope::project_root(&project_path)?;
+    let canonical = scope.validate(&project_path, AccessOp::Search)?;
+    let path_str = canonical
         .to_str()
-        .ok_or_else(|| "Invalid project path encoding".to_string())?;
+        .ok_or_else(|| "Invalid project path encoding".to_string())?
+        .to_string();
+    let path_str = path_str.as_str();
+
+    let json_mode = output.as_deref() == Some("json");
 
     // Build ripgrep args dynamically
-    let mut rg_args: Vec<String> = vec![
-        "--column".into(),
-        "--line-number".into(),
-        "--no-heading".into(),
-        "--color=never".into(),
-    ];
+    let mut rg_args: Vec<String> = if json_mode {
+        vec!["--json".into()]
+    } else {
+        vec![
+            "--column".into(),
+            "--line-number".into(),
+            "--no-heading".into(),
+            "--color=never".into(),
+        ]
+    };
 
     if let Some(mc) = max_count {
         rg_args.push(format!("--max-count={}", mc));
@@ -686,7 +1303,7 @@ pub async fn search_project(
     let arg_refs: Vec<&str> = rg_args.iter().map(|s| s.as_str()).collect();
 
     // LAYER 2: Execute ripgrep sidecar (scoped via ACL validators)
-    let output = app
+    let rg_output = app
         .shell()
         .sidecar("rg")
         .map_err(|e| format!("Failed to spawn ripgrep sidecar: {e}"))?
@@ -695,19 +1312,108 @@ pub async fn search_project(
         .await
         .map_err(|e| format!("ripgrep execution failed: {e}"))?;
 
-    if !output.status.success() && !output.stderr.is_empty() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    if !rg_output.status.success() && !rg_output.stderr.is_empty() {
+        let stderr = String::from_utf8_lossy(&rg_output.stderr);
         // Exit code 1 from rg means "no matches" — not an error
-        if output.status.code() != Some(1) {
+        if rg_output.status.code() != Some(1) {
             return Err(format!("ripgrep error: {stderr}"));
         }
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    let stdout = String::from_utf8_lossy(&rg_output.stdout);
+
+    if !json_mode {
+        return Ok(stdout.into_owned());
+    }
+
+    // Parse ripgrep's line-delimited JSON events into typed hits.
+    let hits = parse_rg_json(&stdout);
+    serde_json::to_string(&hits).map_err(|e| format!("Serialization error: {e}"))
+}
+
+/// Parse ripgrep `--json` event stream, keeping only `match` events.
+fn parse_rg_json(stdout: &str) -> Vec<SearchHit> {
+    let mut hits = Vec::new();
+    for line in stdout.lines() {
+        let event: serde_json::Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if event.get("type").and_then(|t| t.as_str()) != Some("match") {
+            continue;
+        }
+        let data = match event.get("data") {
+            Some(d) => d,
+            None => continue,
+        };
+        let path = data
+            .pointer("/path/text")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let line_number = data
+            .get("line_number")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let matched_line = data
+            .pointer("/lines/text")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim_end_matches('\n')
+            .to_string();
+        let submatches = data
+            .get("submatches")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|sm| {
+                        Some(SubMatch {
+                            start: sm.get("start")?.as_u64()? as usize,
+                            end: sm.get("end")?.as_u64()? as usize,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        hits.push(SearchHit {
+            path,
+            line_number,
+            line: matched_line,
+            submatches,
+        });
+    }
+    hits
+}
+
+/// Tagged result of [`read_file`]: plain text, or a base64 data URL for images.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ReadFileResult {
+    /// UTF-8 text content (with any requested line range applied).
+    Text { content: String },
+    /// An image encoded as a `data:image/<subtype>;base64,…` URL, plus the
+    /// sha256 of that URL so callers can dedupe identical images.
+    Image { data_url: String, hash: String },
+}
+
+/// Map an image file extension to its `image/<subtype>` MIME subtype.
+fn image_subtype(ext: &str) -> Option<&'static str> {
+    match ext.to_ascii_lowercase().as_str() {
+        "png" => Some("png"),
+        "jpg" | "jpeg" => Some("jpeg"),
+        "webp" => Some("webp"),
+        "gif" => Some("gif"),
+        _ => None,
+    }
 }
 
 /// Read the contents of a file within a project directory, optionally by line range.
 ///
+/// Text files are returned as `{ "kind": "text", "content": … }`.  Image files
+/// (png/jpeg/jpg/webp/gif) are returned as `{ "kind": "image", "data_url": …,
+/// "hash": … }` where `hash` is the sha256 of the data URL, so the frontend
+/// can route text into the prompt and images into a multimodal message.
+///
 /// # Security
 /// - `file_path` must be absolute and inside `project_root` (canonicalize + starts_with).
 #[tauri::command]
@@ -716,81 +1422,183 @@ pub async fn read_file(
     project_root: PathBuf,
     start_line: Option<u32>,
     end_line: Option<u32>,
-) -> Result<String, String> {
-    if !file_path.is_absolute() {
-        return Err(format!("file_path must be absolute: '{}'", file_path.display()));
-    }
-    if !project_root.is_absolute() {
-        return Err(format!("project_root must be absolute: '{}'", project_root.display()));
-    }
-
-    let canonical_root = project_root
-        .canonicalize()
-        .map_err(|e| format!("Cannot canonicalize project_root: {e}"))?;
-    let canonical_file = file_path
-        .canonicalize()
-        .map_err(|e| format!("Cannot canonicalize file_path: {e}"))?;
-
-    if !canonical_file.starts_with(&canonical_root) {
-        return Err(format!(
-            "Security Violation: file_path '{}' is outside project_root '{}'",
-            file_path.display(),
-            project_root.display()
-        ));
+) -> Result<ReadFileResult, String> {
+    use crate::scope::{AccessOp, AccessScope};
+    let scope = AccessScope::project_root(&project_root)?;
+    let canonical_file = scope.validate(&file_path, AccessOp::Read)?;
+
+    // Images: return a base64 data URL plus the sha256 of that URL.
+    if let Some(subtype) = canonical_file
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(image_subtype)
+    {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        use sha2::{Digest, Sha256};
+
+        let bytes = std::fs::read(&canonical_file)
+            .map_err(|e| format!("Failed to read file: {e}"))?;
+        let data_url = format!("data:image/{subtype};base64,{}", STANDARD.encode(&bytes));
+        let hash = format!("{:x}", Sha256::digest(data_url.as_bytes()));
+        return Ok(ReadFileResult::Image { data_url, hash });
     }
 
     let content = std::fs::read_to_string(&canonical_file)
         .map_err(|e| format!("Failed to read file: {e}"))?;
 
     // Apply optional line range (1-indexed)
-    match (start_line, end_line) {
+    let content = match (start_line, end_line) {
         (Some(start), Some(end)) => {
             let lines: Vec<&str> = content.lines().collect();
             let s = (start as usize).saturating_sub(1).min(lines.len());
             let e = (end as usize).min(lines.len());
-            Ok(lines[s..e].join("\n"))
+            lines[s..e].join("\n")
         }
         (Some(start), None) => {
             let lines: Vec<&str> = content.lines().collect();
             let s = (start as usize).saturating_sub(1).min(lines.len());
-            Ok(lines[s..].join("\n"))
+            lines[s..].join("\n")
         }
-        _ => Ok(content),
-    }
+        _ => content,
+    };
+
+    Ok(ReadFileResult::Text { content })
 }
 
-/// List files and directories within a project directory.
+/// Load one or more files and/or directories into a single context blob for
+/// LLM ingestion.
 ///
 /// # Security
-/// - `dir_path` must be absolute and inside `project_root` (canonicalize + starts_with).
+/// - Each path must be absolute and inside `project_root` (canonicalize +
+///   `starts_with`), using the same gate as the other file commands.
+///
+/// Directories are walked recursively (reusing the `list_directory` traversal
+/// style); binary files are skipped.  Each text file is prefixed with a
+/// `===== <relative/path> =====` header, and when more than one file is
+/// loaded the whole blob is wrapped so the model can tell files apart.  When
+/// `max_bytes` is set the output is truncated with a trailing `… [truncated]`
+/// marker.
 #[tauri::command]
-pub async fn list_directory(
-    dir_path: PathBuf,
+pub async fn load_context(
+    paths: Vec<PathBuf>,
     project_root: PathBuf,
-    max_depth: Option<u32>,
+    max_bytes: Option<u64>,
 ) -> Result<String, String> {
-    if !dir_path.is_absolute() {
-        return Err(format!("dir_path must be absolute: '{}'", dir_path.display()));
-    }
     if !project_root.is_absolute() {
         return Err(format!("project_root must be absolute: '{}'", project_root.display()));
     }
-
     let canonical_root = project_root
         .canonicalize()
         .map_err(|e| format!("Cannot canonicalize project_root: {e}"))?;
-    let canonical_dir = dir_path
-        .canonicalize()
-        .map_err(|e| format!("Cannot canonicalize dir_path: {e}"))?;
 
-    if !canonical_dir.starts_with(&canonical_root) {
-        return Err(format!(
-            "Security Violation: dir_path '{}' is outside project_root '{}'",
-            dir_path.display(),
-            project_root.display()
-        ));
+    // Collect canonical text files, walking directories recursively.
+    fn collect(current: &std::path::Path, root: &std::path::Path, out: &mut Vec<PathBuf>) {
+        let read = match std::fs::read_dir(current) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        for entry in read.flatten() {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(ft) if ft.is_dir() => collect(&path, root, out),
+                Ok(ft) if ft.is_file() => {
+                    if let Ok(c) = path.canonicalize() {
+                        if c.starts_with(root) {
+                            out.push(c);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
     }
 
+    let mut files: Vec<PathBuf> = Vec::new();
+    for p in &paths {
+        if !p.is_absolute() {
+            return Err(format!("path must be absolute: '{}'", p.display()));
+        }
+        let canonical = p
+            .canonicalize()
+            .map_err(|e| format!("Cannot canonicalize '{}': {e}", p.display()))?;
+        if !canonical.starts_with(&canonical_root) {
+            return Err(format!(
+                "Security Violation: path '{}' is outside project_root '{}'",
+                p.display(),
+                project_root.display()
+            ));
+        }
+        if canonical.is_dir() {
+            collect(&canonical, &canonical_root, &mut files);
+        } else {
+            files.push(canonical);
+        }
+    }
+
+    // Read each file as text, skipping binaries (invalid UTF-8 / embedded NUL).
+    let mut sections: Vec<String> = Vec::new();
+    for file in &files {
+        let bytes = match std::fs::read(file) {
+            Ok(b) => b,
+            Err(_) => continue,
+        };
+        if bytes.contains(&0) {
+            continue; // binary
+        }
+        let text = match String::from_utf8(bytes) {
+            Ok(t) => t,
+            Err(_) => continue, // binary
+        };
+        let rel = file
+            .strip_prefix(&canonical_root)
+            .map(|r| r.display().to_string().replace('\\', "/"))
+            .unwrap_or_else(|_| file.display().to_string());
+        sections.push(format!("===== {rel} =====\n{text}"));
+    }
+
+    // Wrap when multiple files are present so the model can delimit them.
+    let mut blob = if sections.len() > 1 {
+        format!(
+            "<context files=\"{}\">\n{}\n</context>",
+            sections.len(),
+            sections.join("\n\n")
+        )
+    } else {
+        sections.join("\n\n")
+    };
+
+    // Enforce max_bytes by truncating on a char boundary.
+    if let Some(max) = max_bytes {
+        let max = max as usize;
+        const MARKER: &str = "\n… [truncated]";
+        if blob.len() > max {
+            let budget = max.saturating_sub(MARKER.len());
+            let mut cut = budget.min(blob.len());
+            while cut > 0 && !blob.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            blob.truncate(cut);
+            blob.push_str(MARKER);
+        }
+    }
+
+    Ok(blob)
+}
+
+/// List files and directories within a project directory.
+///
+/// # Security
+/// - `dir_path` must be absolute and inside `project_root` (canonicalize + starts_with).
+#[tauri::command]
+pub async fn list_directory(
+    dir_path: PathBuf,
+    project_root: PathBuf,
+    max_depth: Option<u32>,
+) -> Result<String, String> {
+    use crate::scope::{AccessOp, AccessScope};
+    let scope = AccessScope::project_root(&project_root)?;
+    let canonical_dir = scope.validate(&dir_path, AccessOp::List)?;
+
     let depth = max_depth.unwrap_or(3);
     let mut entries = Vec::new();
 
@@ -846,17 +1654,12 @@ pub async fn glob_find(
     file_type: Option<String>,
     max_results: Option<u32>,
 ) -> Result<String, String> {
-    let path_str_lossy = project_path.to_string_lossy();
-    let is_absolute = project_path.is_absolute() || path_str_lossy.starts_with('/');
-
-    if !is_absolute {
-        return Err(format!(
-            "Project path must be absolute: '{}'",
-            project_path.display()
-        ));
-    }
-
-    let path_str = project_path
+    // Route through the central ACL so glob_find canonicalizes (it previously
+    // didn't) and rejects paths escaping the project root via symlinks.
+    use crate::scope::{AccessOp, AccessScope};
+    let scope = AccessScope::project_root(&project_path)?;
+    let canonical = scope.validate(&project_path, AccessOp::Search)?;
+    let path_str = canonical
         .to_str()
         .ok_or_else(|| "Invalid project path encoding".to_string())?;
 
@@ -898,7 +1701,7 @@ pub async fn glob_find(
         };
         if include {
             let rel = p
-                .strip_prefix(&project_path)
+                .strip_prefix(&canonical)
                 .map(|r| r.display().to_string())
                 .unwrap_or_else(|_| p.display().to_string());
             results.push(serde_json::json!({
@@ -922,8 +1725,122 @@ pub struct LicenseResult {
     pub expiry: Option<String>,
 }
 
+/// Cached result of the last successful validation, used as an offline
+/// fallback within the grace window.
+#[derive(Serialize, Deserialize)]
+struct LicenseCache {
+    tier: String,
+    expiry: Option<String>,
+    /// Seconds since the Unix epoch when this result was last confirmed.
+    validated_at: u64,
+}
+
+fn epoch_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn license_cache_path(app: &AppHandle) -> Option<PathBuf> {
+    use tauri::Manager;
+    app.path()
+        .app_config_dir()
+        .ok()
+        .map(|d| d.join("license_cache.json"))
+}
+
+fn write_license_cache(app: &AppHandle, tier: &str, expiry: &Option<String>) {
+    if let Some(path) = license_cache_path(app) {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let cache = LicenseCache {
+            tier: tier.to_string(),
+            expiry: expiry.clone(),
+            validated_at: epoch_secs(),
+        };
+        if let Ok(json) = serde_json::to_string_pretty(&cache) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+}
+
+fn read_license_cache(app: &AppHandle) -> Option<LicenseCache> {
+    let path = license_cache_path(app)?;
+    let raw = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Verify a Keygen cryptographic license key offline.
+///
+/// Cryptographic keys take the form `key/<payload>.<signature>`, where the
+/// Ed25519 signature covers the literal `key/<payload>` prefix and `<payload>`
+/// base64-decodes to a JSON document carrying `tier` and `expiry`.  The
+/// verifying public key is embedded at build time via `KEYGEN_VERIFY_KEY`
+/// (hex-encoded).  A valid, unexpired signature is authoritative — no HTTP.
+fn verify_crypto_license(license_key: &str) -> Option<LicenseResult> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    let verify_hex = option_env!("KEYGEN_VERIFY_KEY")?;
+    let verify_bytes: [u8; 32] = hex::decode(verify_hex.trim()).ok()?.try_into().ok()?;
+    let verifying_key = VerifyingKey::from_bytes(&verify_bytes).ok()?;
+
+    // Split `key/<payload>.<signature>`.
+    let (signing_input, sig_b64) = license_key.rsplit_once('.')?;
+    let payload_b64 = signing_input.strip_prefix("key/")?;
+
+    let sig_bytes: [u8; 64] = URL_SAFE_NO_PAD.decode(sig_b64).ok()?.try_into().ok()?;
+    let signature = Signature::from_bytes(&sig_bytes);
+    verifying_key
+        .verify(signing_input.as_bytes(), &signature)
+        .ok()?;
+
+    // Signature is valid — decode the payload for tier/expiry.
+    let payload = URL_SAFE_NO_PAD.decode(payload_b64).ok()?;
+    let doc: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+    let tier = doc
+        .pointer("/tier")
+        .or_else(|| doc.pointer("/metadata/tier"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("listener")
+        .to_string();
+    let expiry = doc
+        .pointer("/expiry")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    // Reject an expired signature (RFC3339 compared against now).
+    if let Some(ref exp) = expiry {
+        if let Ok(exp_time) = chrono::DateTime::parse_from_rfc3339(exp) {
+            if exp_time.timestamp() < epoch_secs() as i64 {
+                return Some(LicenseResult {
+                    valid: false,
+                    tier: "listener".into(),
+                    expiry: Some(exp.clone()),
+                });
+            }
+        }
+    }
+
+    Some(LicenseResult {
+        valid: true,
+        tier,
+        expiry,
+    })
+}
+
 #[tauri::command]
-pub async fn validate_license(license_key: String) -> Result<LicenseResult, String> {
+pub async fn validate_license(app: AppHandle, license_key: String) -> Result<LicenseResult, String> {
+    // 1. Offline cryptographic verification — authoritative, no network.
+    if let Some(result) = verify_crypto_license(&license_key) {
+        if result.valid {
+            write_license_cache(&app, &result.tier, &result.expiry);
+        }
+        return Ok(result);
+    }
+
     let account_id = std::env::var("KEYGEN_ACCOUNT_ID").unwrap_or_default();
     if account_id.is_empty() {
         return Ok(LicenseResult {
@@ -942,36 +1859,68 @@ pub async fn validate_license(license_key: String) -> Result<LicenseResult, Stri
         "meta": { "key": license_key }
     });
 
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(&url)
-        .header("Content-Type", "application/vnd.api+json")
-        .header("Accept", "application/vnd.api+json")
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| format!("License validation request failed: {e}"))?;
+    // Online freshness check. If the network is down or the request fails,
+    // fall back to the last successful result within the grace window before
+    // downgrading — so paid tiers survive flaky connectivity.
+    let grace_days: u64 = std::env::var("VOCO_LICENSE_GRACE_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(7);
+    let grace_secs = grace_days * 24 * 60 * 60;
 
-    let data: serde_json::Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse license response: {e}"))?;
-
-    let valid = data
-        .pointer("/meta/valid")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-
-    let tier = data
-        .pointer("/data/attributes/metadata/tier")
-        .and_then(|v| v.as_str())
-        .unwrap_or("listener")
-        .to_string();
-
-    let expiry = data
-        .pointer("/data/attributes/expiry")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    Ok(LicenseResult { valid, tier, expiry })
+    let client = reqwest::Client::new();
+    let online = async {
+        let resp = client
+            .post(&url)
+            .header("Content-Type", "application/vnd.api+json")
+            .header("Accept", "application/vnd.api+json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("License validation request failed: {e}"))?;
+        resp.json::<serde_json::Value>()
+            .await
+            .map_err(|e| format!("Failed to parse license response: {e}"))
+    }
+    .await;
+
+    match online {
+        Ok(data) => {
+            let valid = data
+                .pointer("/meta/valid")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let tier = data
+                .pointer("/data/attributes/metadata/tier")
+                .and_then(|v| v.as_str())
+                .unwrap_or("listener")
+                .to_string();
+            let expiry = data
+                .pointer("/data/attributes/expiry")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
+            if valid {
+                write_license_cache(&app, &tier, &expiry);
+            }
+            Ok(LicenseResult { valid, tier, expiry })
+        }
+        Err(_) => {
+            // Offline: honor the cached tier while still inside the grace window.
+            if let Some(cache) = read_license_cache(&app) {
+                if epoch_secs().saturating_sub(cache.validated_at) <= grace_secs {
+                    return Ok(LicenseResult {
+                        valid: true,
+                        tier: cache.tier,
+                        expiry: cache.expiry,
+                    });
+                }
+            }
+            Ok(LicenseResult {
+                valid: false,
+                tier: "listener".into(),
+                expiry: None,
+            })
+        }
+    }
 }
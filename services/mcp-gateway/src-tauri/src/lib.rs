@@ -1,14 +1,20 @@
 mod audio;
 mod backend;
 mod commands;
+mod policy;
+mod proxy;
+mod scope;
 mod screen;
+mod watcher;
 
 use std::sync::Arc;
 
 use tauri::Manager;
 
 use audio::AudioState;
+use audio::MicState;
 use backend::BackendState;
+use watcher::WatcherState;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -19,12 +25,27 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .manage(AudioState::new())
+        .manage(MicState::new())
+        .manage(WatcherState::new())
         .manage(Arc::new(BackendState::new()))
         .setup(|app| {
             // Phase 3: start the background screen capture thread.
             // Frames accumulate silently; get_recent_frames() reads them on demand.
             screen::start_capture_thread();
 
+            // Single-port reverse proxy fronting the engine + LiteLLM upstreams.
+            proxy::start_proxy();
+
+            // Start the audio output thread (it emits playback lifecycle events).
+            app.state::<AudioState>().start(app.handle().clone());
+
+            // Re-apply the user's persisted output-device choice.
+            audio::apply_saved_device(app.handle(), &app.state::<AudioState>());
+
+            // Start mic capture + VAD; speech onset triggers automatic barge-in.
+            let audio_tx = app.state::<AudioState>().sender()?;
+            app.state::<MicState>().start(app.handle().clone(), audio_tx);
+
             // Auto-start backend services (cognitive-engine + LiteLLM).
             // In dev mode this only polls health; in release it spawns processes.
             let state: Arc<BackendState> = app.state::<Arc<BackendState>>().inner().clone();
@@ -36,6 +57,7 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::search_project,
             commands::read_file,
+            commands::load_context,
             commands::list_directory,
             commands::glob_find,
             commands::write_file,
@@ -46,10 +68,24 @@ pub fn run() {
             commands::open_url,
             commands::scan_security,
             commands::validate_license,
+            watcher::watch_project,
+            watcher::unwatch_project,
             audio::play_native_audio,
+            audio::play_native_audio_opus,
+            audio::play_native_audio_pcm,
             audio::halt_native_audio,
+            audio::duck_native_audio,
+            audio::restore_native_audio,
+            audio::list_audio_devices,
+            audio::select_audio_device,
+            audio::set_vad_threshold,
+            audio::set_vad_factor,
+            audio::set_vad_hangover,
             screen::get_recent_frames,
+            screen::enumerate_monitors,
+            screen::set_capture_params,
             backend::get_backend_status,
+            backend::get_backend_logs,
         ])
         .build(tauri::generate_context!())
         .expect("error while building Voco MCP Gateway");
@@ -0,0 +1,106 @@
+//! Per-project command allowlist — a least-privilege capability layer in
+//! front of [`execute_command`](crate::commands::execute_command).
+//!
+//! The "double-lock" in `execute_command` protects the *directory* a command
+//! runs in, but not the *command itself*.  Each authorized project gets a
+//! stored policy file (`.voco/command-policy.json`) listing the command
+//! prefixes it is allowed to run, plus an optional approve-on-first-use flow:
+//! a command whose leading executable matches no allowed glob is either
+//! rejected outright or surfaced to the user for approval, and an approval is
+//! recorded back into the policy so it is remembered next time.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// The command policy persisted per project.
+#[derive(Serialize, Deserialize)]
+pub struct CommandPolicy {
+    /// Glob patterns matched against the command's leading executable
+    /// (e.g. `git`, `npm`, `cargo*`).
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    /// When true, a command that matches no glob prompts the user for approval
+    /// instead of being rejected; an approved prefix is appended to `allowed`.
+    #[serde(default)]
+    pub approve_on_first_use: bool,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        // Fail closed: nothing is allowed until the user approves it.
+        Self {
+            allowed: Vec::new(),
+            approve_on_first_use: true,
+        }
+    }
+}
+
+/// Path to a project's policy file (`<project>/.voco/command-policy.json`).
+fn policy_path(project_root: &Path) -> std::path::PathBuf {
+    project_root.join(".voco").join("command-policy.json")
+}
+
+impl CommandPolicy {
+    /// Load the policy for `project_root`, returning the default (approve-on-
+    /// first-use, empty allowlist) when no policy file exists yet.
+    pub fn load(project_root: &Path) -> Self {
+        let path = policy_path(project_root);
+        std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist the policy to `<project>/.voco/command-policy.json`.
+    pub fn save(&self, project_root: &Path) -> Result<(), String> {
+        let path = policy_path(project_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Cannot create policy dir: {e}"))?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| format!("Serialization error: {e}"))?;
+        std::fs::write(&path, json).map_err(|e| format!("Cannot write policy: {e}"))
+    }
+
+    /// Returns true if `executable` matches any allowed glob pattern.
+    pub fn permits(&self, executable: &str) -> bool {
+        self.allowed.iter().any(|pat| match glob::Pattern::new(pat) {
+            Ok(p) => p.matches(executable),
+            // A malformed glob degrades to an exact-string comparison.
+            Err(_) => pat == executable,
+        })
+    }
+
+    /// Record `executable` as allowed and persist.
+    pub fn allow(&mut self, executable: &str, project_root: &Path) -> Result<(), String> {
+        if !self.allowed.iter().any(|p| p == executable) {
+            self.allowed.push(executable.to_string());
+        }
+        self.save(project_root)
+    }
+}
+
+/// Extract the leading executable token from a shell command string.
+///
+/// Handles leading environment assignments (`FOO=bar cmd …`) by skipping any
+/// `KEY=VALUE` prefixes, mirroring how `sh` resolves the program to run.
+pub fn leading_executable(command: &str) -> Option<String> {
+    for token in command.split_whitespace() {
+        // Skip `VAR=value` environment prefixes (the name before `=` must be
+        // a shell identifier; the value may be anything, including a path).
+        if let Some((name, _)) = token.split_once('=') {
+            if !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                continue;
+            }
+        }
+        // Strip a directory component so `./node_modules/.bin/foo` matches `foo`.
+        let exe = token.rsplit(['/', '\\']).next().unwrap_or(token);
+        return Some(exe.to_string());
+    }
+    None
+}
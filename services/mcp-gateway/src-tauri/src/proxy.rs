@@ -0,0 +1,230 @@
+//! Single-port reverse proxy fronting the cognitive-engine (:8001) and the
+//! LiteLLM proxy (:4000).
+//!
+//! The frontend previously had to know both upstream ports directly.  This
+//! embedded proxy binds one local port and routes by path prefix:
+//!
+//! - `/engine/*` → `127.0.0.1:8001`
+//! - `/llm/*`    → `127.0.0.1:4000`
+//! - `/health`   → an aggregated readiness check over both upstreams
+//!
+//! It is modeled on a minimal proxy worker: accept a connection, parse the
+//! request line to pick the upstream, open a connection to that backend,
+//! forward the (prefix-stripped) request, and stream bytes both directions
+//! until either side closes.  The proxy keeps no up/down state for an
+//! upstream: each request connects afresh, and a failed connect is answered
+//! with `502 Bad Gateway`.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+const ENGINE_UPSTREAM: &str = "127.0.0.1:8001";
+const LLM_UPSTREAM: &str = "127.0.0.1:4000";
+
+/// Local port the proxy binds (env `VOCO_PROXY_PORT`, default 8900).
+fn proxy_port() -> u16 {
+    std::env::var("VOCO_PROXY_PORT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(8900)
+}
+
+/// Start the reverse proxy on a detached thread.  Call once at app startup.
+pub fn start_proxy() {
+    let port = proxy_port();
+    std::thread::spawn(move || {
+        let listener = match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("[Proxy] Failed to bind 127.0.0.1:{port}: {e}");
+                return;
+            }
+        };
+        eprintln!("[Proxy] Listening on 127.0.0.1:{port} (/engine/*, /llm/*, /health).");
+        for stream in listener.incoming().flatten() {
+            std::thread::spawn(move || handle_conn(stream));
+        }
+    });
+}
+
+/// Handle one client connection: read the request head, route, and stream.
+fn handle_conn(mut client: TcpStream) {
+    let _ = client.set_read_timeout(Some(Duration::from_secs(30)));
+
+    // Read up to the end of the request headers.
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    loop {
+        match client.read(&mut byte) {
+            Ok(0) => return,
+            Ok(_) => {
+                head.push(byte[0]);
+                if head.ends_with(b"\r\n\r\n") {
+                    break;
+                }
+                if head.len() > 16 * 1024 {
+                    return; // header too large — bail
+                }
+            }
+            Err(_) => return,
+        }
+    }
+
+    let head_str = String::from_utf8_lossy(&head).into_owned();
+    let request_line = head_str.lines().next().unwrap_or("");
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("/");
+
+    // Aggregated health endpoint — answered directly, no upstream stream.
+    if path == "/health" {
+        respond_health(&mut client);
+        return;
+    }
+
+    let (upstream, stripped) = if let Some(rest) = strip_route(path, "/engine") {
+        (ENGINE_UPSTREAM, normalize(rest))
+    } else if let Some(rest) = strip_route(path, "/llm") {
+        (LLM_UPSTREAM, normalize(rest))
+    } else {
+        respond_status(&mut client, "404 Not Found", "Unknown route");
+        return;
+    };
+
+    let mut server = match TcpStream::connect(upstream) {
+        Ok(s) => s,
+        Err(_) => {
+            respond_status(&mut client, "502 Bad Gateway", "Upstream unavailable");
+            return;
+        }
+    };
+    // Bound the upstream read so a wedged backend can't pin a proxy thread.
+    let _ = server.set_read_timeout(Some(Duration::from_secs(30)));
+
+    // Rewrite the request target in the first line and force `Connection:
+    // close` on the forwarded request.  Clients default to keep-alive on
+    // HTTP/1.1, which would otherwise leave the upstream holding the socket
+    // open and block the response `copy` below indefinitely (leaking both
+    // proxy threads).  Closing after one response keeps the stream framed.
+    let rewritten_line = format!("{method} {stripped} HTTP/1.1\r\n");
+    let headers = head_str.split_once("\r\n").map(|(_, rest)| rest).unwrap_or("");
+    let forwarded_headers = force_connection_close(headers);
+    if server.write_all(rewritten_line.as_bytes()).is_err()
+        || server.write_all(forwarded_headers.as_bytes()).is_err()
+    {
+        return;
+    }
+
+    // Stream both directions until either side closes.
+    let mut client_rx = match client.try_clone() {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    let mut server_rx = match server.try_clone() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+
+    let up = std::thread::spawn(move || {
+        let _ = std::io::copy(&mut client_rx, &mut server);
+    });
+    let _ = std::io::copy(&mut server_rx, &mut client);
+    let _ = up.join();
+}
+
+/// Drop any client `Connection`/`Keep-Alive` headers and append
+/// `Connection: close`, leaving the terminating blank line intact.  Takes the
+/// header block (everything after the request line, ending in `\r\n\r\n`).
+fn force_connection_close(headers: &str) -> String {
+    let mut out = String::with_capacity(headers.len() + 19);
+    for line in headers.split("\r\n") {
+        if line.is_empty() {
+            break; // reached the blank line that ends the head
+        }
+        let name = line.split(':').next().unwrap_or("").trim();
+        if name.eq_ignore_ascii_case("connection") || name.eq_ignore_ascii_case("keep-alive") {
+            continue;
+        }
+        out.push_str(line);
+        out.push_str("\r\n");
+    }
+    out.push_str("Connection: close\r\n\r\n");
+    out
+}
+
+/// Match `prefix` only on a path-segment boundary: either `path` equals the
+/// prefix exactly (`/engine`) or the prefix is followed by `/` (`/engine/...`).
+/// Returns the remainder after the prefix, so `/engineering` does not route to
+/// the engine upstream.
+fn strip_route<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    let rest = path.strip_prefix(prefix)?;
+    if rest.is_empty() || rest.starts_with('/') {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// Turn the remainder left by [`strip_route`] into a request target with a
+/// leading slash (an empty remainder becomes `/`).
+fn normalize(rest: &str) -> String {
+    if rest.is_empty() {
+        "/".to_string()
+    } else if rest.starts_with('/') {
+        rest.to_string()
+    } else {
+        format!("/{rest}")
+    }
+}
+
+/// Write a tiny HTTP response with a plain-text body.
+fn respond_status(client: &mut TcpStream, status: &str, body: &str) {
+    let resp = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = client.write_all(resp.as_bytes());
+}
+
+/// Aggregate both upstream `/health` endpoints into one readiness response.
+fn respond_health(client: &mut TcpStream) {
+    let engine = upstream_healthy(ENGINE_UPSTREAM);
+    let llm = upstream_healthy(LLM_UPSTREAM);
+    let body = format!(
+        "{{\"engine\":{engine},\"llm\":{llm},\"ok\":{}}}",
+        engine && llm
+    );
+    let status = if engine && llm {
+        "200 OK"
+    } else {
+        "503 Service Unavailable"
+    };
+    let resp = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = client.write_all(resp.as_bytes());
+}
+
+/// Minimal blocking health probe of an upstream `host:port`.
+fn upstream_healthy(addr: &str) -> bool {
+    let sock = match addr.parse() {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    let mut stream = match TcpStream::connect_timeout(&sock, Duration::from_secs(2)) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+    let request = format!("GET /health HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n");
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+    let mut buf = [0u8; 256];
+    match stream.read(&mut buf) {
+        Ok(n) if n > 0 => String::from_utf8_lossy(&buf[..n]).contains("200"),
+        _ => false,
+    }
+}
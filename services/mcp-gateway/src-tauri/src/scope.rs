@@ -0,0 +1,168 @@
+//! Capability-scoped access control for the file commands.
+//!
+//! Every file command used to re-implement the same `is_absolute` +
+//! `canonicalize` + `starts_with(project_root)` dance, which was error-prone:
+//! `glob_find` never canonicalized at all, and a symlink *inside* the root
+//! pointing *outside* it slipped past `starts_with`.  This module consolidates
+//! that logic into one audited place, analogous to Tauri's capability model.
+//!
+//! An [`AccessScope`] holds an allowlist of canonical roots;
+//! [`AccessScope::validate`] resolves symlinks, confirms the target lands
+//! inside an allowed root, and returns the canonical path (or a typed
+//! [`AccessError`]).  [`AccessScope::validate_new`] does the same for a path
+//! that may not exist yet (the write path), validating the parent and final
+//! component so a symlinked leaf cannot redirect the write outside the root.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The operation a path is being validated for.  Today it only sharpens error
+/// messages, but it distinguishes reads from the riskier write path.
+#[derive(Clone, Copy, Debug)]
+pub enum AccessOp {
+    Read,
+    Write,
+    List,
+    Search,
+}
+
+impl fmt::Display for AccessOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AccessOp::Read => "read",
+            AccessOp::Write => "write",
+            AccessOp::List => "list",
+            AccessOp::Search => "search",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A typed access-control failure.
+#[derive(Debug)]
+pub enum AccessError {
+    /// The supplied path was not absolute.
+    NotAbsolute(PathBuf),
+    /// The path could not be canonicalized (missing, or a broken symlink).
+    Canonicalize(PathBuf, String),
+    /// The canonical path (symlinks resolved) escapes every allowed root.
+    OutsideScope { path: PathBuf, op: AccessOp },
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessError::NotAbsolute(p) => {
+                write!(f, "Path must be absolute: '{}'", p.display())
+            }
+            AccessError::Canonicalize(p, e) => {
+                write!(f, "Cannot canonicalize '{}': {e}", p.display())
+            }
+            AccessError::OutsideScope { path, op } => write!(
+                f,
+                "Security Violation: {op} of '{}' is outside the allowed scope",
+                path.display()
+            ),
+        }
+    }
+}
+
+// Commands surface errors as plain `String`; let `?` convert directly.
+impl From<AccessError> for String {
+    fn from(e: AccessError) -> Self {
+        e.to_string()
+    }
+}
+
+/// An allowlist of canonical roots.
+pub struct AccessScope {
+    roots: Vec<PathBuf>,
+}
+
+impl AccessScope {
+    /// Build a scope rooted at a single project directory, canonicalizing it
+    /// so symlinked roots are compared by their real location.
+    pub fn project_root(root: &Path) -> Result<Self, AccessError> {
+        if !root.is_absolute() {
+            return Err(AccessError::NotAbsolute(root.to_path_buf()));
+        }
+        let canonical = root
+            .canonicalize()
+            .map_err(|e| AccessError::Canonicalize(root.to_path_buf(), e.to_string()))?;
+        Ok(Self {
+            roots: vec![canonical],
+        })
+    }
+
+    /// True if `canonical` lands inside an allowed root.
+    fn contains(&self, canonical: &Path) -> bool {
+        self.roots.iter().any(|r| canonical.starts_with(r))
+    }
+
+    /// Validate `path` for `op`, resolving symlinks and confirming it lands
+    /// inside an allowed root.  Returns the canonical path.  The path must
+    /// already exist (use [`validate_new`](Self::validate_new) otherwise).
+    pub fn validate(&self, path: &Path, op: AccessOp) -> Result<PathBuf, AccessError> {
+        if !path.is_absolute() {
+            return Err(AccessError::NotAbsolute(path.to_path_buf()));
+        }
+        let canonical = path
+            .canonicalize()
+            .map_err(|e| AccessError::Canonicalize(path.to_path_buf(), e.to_string()))?;
+
+        if !self.contains(&canonical) {
+            return Err(AccessError::OutsideScope {
+                path: canonical,
+                op,
+            });
+        }
+
+        Ok(canonical)
+    }
+
+    /// Validate a path that may not exist yet — the write path.  The parent
+    /// directory must resolve inside an allowed root; the final component is
+    /// then appended to the *canonical* parent.  If a leaf already exists it is
+    /// resolved (following a symlink) and re-checked, so a symlinked final
+    /// component cannot redirect the write outside the scope.  Returns the
+    /// canonical path to operate on.
+    pub fn validate_new(&self, path: &Path, op: AccessOp) -> Result<PathBuf, AccessError> {
+        if !path.is_absolute() {
+            return Err(AccessError::NotAbsolute(path.to_path_buf()));
+        }
+        let parent = path.parent().ok_or_else(|| {
+            AccessError::Canonicalize(path.to_path_buf(), "path has no parent directory".to_string())
+        })?;
+        let canonical_parent = parent
+            .canonicalize()
+            .map_err(|e| AccessError::Canonicalize(parent.to_path_buf(), e.to_string()))?;
+        if !self.contains(&canonical_parent) {
+            return Err(AccessError::OutsideScope {
+                path: canonical_parent,
+                op,
+            });
+        }
+
+        let file_name = path.file_name().ok_or_else(|| {
+            AccessError::Canonicalize(path.to_path_buf(), "path has no final component".to_string())
+        })?;
+        let target = canonical_parent.join(file_name);
+
+        // A pre-existing leaf may itself be a symlink out of the scope; resolve
+        // and re-check it rather than trusting the uncanonicalized path.
+        if target.symlink_metadata().is_ok() {
+            let resolved = target
+                .canonicalize()
+                .map_err(|e| AccessError::Canonicalize(target.clone(), e.to_string()))?;
+            if !self.contains(&resolved) {
+                return Err(AccessError::OutsideScope {
+                    path: resolved,
+                    op,
+                });
+            }
+            return Ok(resolved);
+        }
+
+        Ok(target)
+    }
+}
@@ -1,14 +1,15 @@
 //! Voco Eyes — rolling screen frame buffer (Phase 3).
 //!
-//! A background thread captures the primary monitor every 500 ms and stores
-//! the last 10 JPEG frames (~5 s of recent history) in a global VecDeque.
+//! A background thread captures the configured monitors every 500 ms and
+//! stores the last 10 JPEG frames (~5 s of recent history) per display in a
+//! global map keyed by monitor id.
 //!
 //! ``get_recent_frames`` is a Tauri command invoked by the React frontend
 //! in response to a ``screen_capture_request`` WebSocket message sent by the
 //! Python cognitive engine when Claude calls the ``analyze_screen`` tool.
 
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     io::Cursor,
     sync::{Arc, Mutex, OnceLock},
     thread,
@@ -16,27 +17,135 @@ use std::{
 };
 
 use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
 
-// Maximum frames to keep in memory (~5 s at 500 ms / frame).
+// Maximum frames to keep in memory per display (~5 s at 500 ms / frame).
 const BUFFER_SIZE: usize = 10;
 // Interval between captures in milliseconds.
 const CAPTURE_INTERVAL_MS: u64 = 500;
-// Maximum dimension for the resized frame (reduces JPEG payload size).
-const MAX_DIM: u32 = 1280;
-// JPEG quality (0-100). 75 gives a good quality/size tradeoff.
-const JPEG_QUALITY: u8 = 75;
+// Default maximum dimension for the resized frame (reduces JPEG payload size).
+const DEFAULT_MAX_DIM: u32 = 1280;
+// Default JPEG quality (0-100). 75 gives a good quality/size tradeoff.
+const DEFAULT_JPEG_QUALITY: u8 = 75;
+// Edge length of the grayscale thumbnail used for perceptual frame diffing.
+const THUMB_DIM: u32 = 32;
+// Default mean-absolute per-pixel difference (0-255 scale) below which a frame
+// is considered a duplicate of the previous one and dropped.
+const DEFAULT_DIFF_THRESHOLD: f64 = 3.0;
+
+/// Resolve the perceptual-diff threshold (env `VOCO_CAPTURE_DIFF_THRESHOLD`).
+fn diff_threshold() -> f64 {
+    std::env::var("VOCO_CAPTURE_DIFF_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_DIFF_THRESHOLD)
+}
 
 // ---------------------------------------------------------------------------
-// Global frame buffer
+// Per-display capture parameters
 // ---------------------------------------------------------------------------
 
-static FRAME_BUFFER: OnceLock<Arc<Mutex<VecDeque<Vec<u8>>>>> = OnceLock::new();
+/// Per-display capture tuning.  Mirrors the display-parameter descriptors used
+/// by VM display backends: an optional crop rectangle plus size/quality caps.
+#[derive(Clone, Deserialize)]
+pub struct DisplayParams {
+    /// Maximum output dimension (longest edge); defaults to [`DEFAULT_MAX_DIM`].
+    #[serde(default)]
+    pub max_dim: Option<u32>,
+    /// JPEG quality 0-100; defaults to [`DEFAULT_JPEG_QUALITY`].
+    #[serde(default)]
+    pub quality: Option<u8>,
+    /// Optional crop rectangle `[x, y, width, height]` in source pixels.
+    #[serde(default)]
+    pub crop: Option<[u32; 4]>,
+}
+
+impl Default for DisplayParams {
+    fn default() -> Self {
+        Self {
+            max_dim: None,
+            quality: None,
+            crop: None,
+        }
+    }
+}
+
+/// Which displays the capture thread targets.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureTargets {
+    /// All connected monitors.
+    All,
+    /// A specific set of monitor ids.
+    Ids(Vec<u32>),
+}
+
+impl Default for CaptureTargets {
+    fn default() -> Self {
+        CaptureTargets::All
+    }
+}
+
+/// Runtime capture configuration shared with the capture thread.
+#[derive(Clone, Default)]
+struct CaptureConfig {
+    targets: CaptureTargets,
+    per_display: HashMap<u32, DisplayParams>,
+}
+
+/// A monitor's identity, returned by [`enumerate_monitors`].
+#[derive(Serialize)]
+pub struct MonitorInfo {
+    pub id: u32,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub is_primary: bool,
+}
 
-fn get_buffer() -> Arc<Mutex<VecDeque<Vec<u8>>>> {
-    Arc::clone(
-        FRAME_BUFFER
-            .get_or_init(|| Arc::new(Mutex::new(VecDeque::with_capacity(BUFFER_SIZE)))),
-    )
+/// A single buffered frame plus its monotonic sequence id, returned by
+/// [`get_recent_frames`].  The sequence id lets the vision pipeline see how
+/// many distinct frames were captured across the returned set (ids are only
+/// assigned to frames that survived the perceptual-diff filter).
+#[derive(Serialize)]
+pub struct CapturedFrame {
+    pub seq: u64,
+    /// Base64-encoded JPEG.
+    pub data: String,
+}
+
+// ---------------------------------------------------------------------------
+// Global frame buffers (one VecDeque per display) + config
+// ---------------------------------------------------------------------------
+
+/// A buffered JPEG frame with its monotonic sequence id.
+#[derive(Clone)]
+struct Frame {
+    seq: u64,
+    jpeg: Vec<u8>,
+}
+
+/// Per-display ring buffer plus the state needed for perceptual diffing.
+#[derive(Default)]
+struct DisplayBuffer {
+    frames: VecDeque<Frame>,
+    /// 32×32 grayscale thumbnail of the last *kept* frame.
+    last_thumb: Option<Vec<u8>>,
+    /// Monotonic sequence counter for this display.
+    next_seq: u64,
+}
+
+type Buffers = Arc<Mutex<HashMap<u32, DisplayBuffer>>>;
+
+static FRAME_BUFFERS: OnceLock<Buffers> = OnceLock::new();
+static CAPTURE_CONFIG: OnceLock<Arc<Mutex<CaptureConfig>>> = OnceLock::new();
+
+fn get_buffers() -> Buffers {
+    Arc::clone(FRAME_BUFFERS.get_or_init(|| Arc::new(Mutex::new(HashMap::new()))))
+}
+
+fn get_config() -> Arc<Mutex<CaptureConfig>> {
+    Arc::clone(CAPTURE_CONFIG.get_or_init(|| Arc::new(Mutex::new(CaptureConfig::default()))))
 }
 
 // ---------------------------------------------------------------------------
@@ -49,73 +158,210 @@ fn get_buffer() -> Arc<Mutex<VecDeque<Vec<u8>>>> {
 /// process. All errors are silently swallowed so a permission denial or
 /// monitor change never crashes the app.
 pub fn start_capture_thread() {
-    let buffer = get_buffer();
+    let buffers = get_buffers();
+    let config = get_config();
     thread::spawn(move || loop {
-        capture_one_frame(&buffer);
+        capture_tick(&buffers, &config);
         thread::sleep(Duration::from_millis(CAPTURE_INTERVAL_MS));
     });
 }
 
-fn capture_one_frame(buffer: &Arc<Mutex<VecDeque<Vec<u8>>>>) {
-    // Find the primary monitor. Silently bail on any error.
+/// Capture one frame from every targeted monitor into its per-display buffer.
+fn capture_tick(buffers: &Buffers, config: &Arc<Mutex<CaptureConfig>>) {
     let monitors = match xcap::Monitor::all() {
         Ok(m) => m,
         Err(_) => return,
     };
 
-    let monitor = match monitors.into_iter().find(|m| m.is_primary()) {
-        Some(m) => m,
-        None => return,
-    };
-
-    // Capture as RGBA image.
-    let rgba_image = match monitor.capture_image() {
-        Ok(img) => img,
+    let cfg = match config.lock() {
+        Ok(c) => c.clone(),
         Err(_) => return,
     };
 
-    // Convert RGBA → DynamicImage, resize, then encode as JPEG.
-    let dynamic = image::DynamicImage::ImageRgba8(rgba_image);
-    let resized = dynamic.resize(MAX_DIM, MAX_DIM, image::imageops::FilterType::Nearest);
+    for monitor in monitors {
+        let id = monitor.id();
+        let targeted = match &cfg.targets {
+            CaptureTargets::All => true,
+            CaptureTargets::Ids(ids) => ids.contains(&id),
+        };
+        if !targeted {
+            continue;
+        }
+        let params = cfg.per_display.get(&id).cloned().unwrap_or_default();
+        let processed = match capture_one_frame(&monitor, &params) {
+            Some(p) => p,
+            None => continue,
+        };
+        let thumb = thumbnail(&processed);
 
-    // Encode to JPEG bytes. DynamicImage::write_to strips alpha for JPEG automatically.
-    let mut jpeg_bytes: Vec<u8> = Vec::new();
-    if resized
-        .write_to(&mut Cursor::new(&mut jpeg_bytes), image::ImageFormat::Jpeg)
-        .is_err()
-    {
-        return;
+        if let Ok(mut map) = buffers.lock() {
+            let buf = map.entry(id).or_default();
+
+            // Drop the frame when it is perceptually identical to the last one
+            // we kept — but always keep at least one frame per display.
+            let redundant = buf
+                .last_thumb
+                .as_ref()
+                .map(|prev| mean_abs_diff(prev, &thumb) < diff_threshold())
+                .unwrap_or(false);
+            if redundant && !buf.frames.is_empty() {
+                continue;
+            }
+
+            let jpeg = match encode_jpeg(&processed, params.quality.unwrap_or(DEFAULT_JPEG_QUALITY)) {
+                Some(j) => j,
+                None => continue,
+            };
+
+            let seq = buf.next_seq;
+            buf.next_seq += 1;
+            buf.last_thumb = Some(thumb);
+            if buf.frames.len() >= BUFFER_SIZE {
+                buf.frames.pop_front();
+            }
+            buf.frames.push_back(Frame { seq, jpeg });
+        }
     }
+}
 
-    // Overwrite JPEG quality by re-encoding with the codecs encoder if needed.
-    // (The default write_to JPEG quality is ~75, which is our target — good enough.)
+/// Capture a single monitor and apply the crop/resize steps, returning the
+/// processed image ready for thumbnailing and JPEG encoding.
+fn capture_one_frame(monitor: &xcap::Monitor, params: &DisplayParams) -> Option<image::DynamicImage> {
+    let rgba_image = monitor.capture_image().ok()?;
+    let mut dynamic = image::DynamicImage::ImageRgba8(rgba_image);
 
-    // Push to rolling buffer.
-    let mut buf = match buffer.lock() {
-        Ok(b) => b,
-        Err(_) => return,
-    };
-    if buf.len() >= BUFFER_SIZE {
-        buf.pop_front();
+    // Apply an optional crop rectangle, clamped to the image bounds.
+    if let Some([x, y, w, h]) = params.crop {
+        let (iw, ih) = (dynamic.width(), dynamic.height());
+        if x < iw && y < ih {
+            let w = w.min(iw - x);
+            let h = h.min(ih - y);
+            dynamic = dynamic.crop_imm(x, y, w, h);
+        }
+    }
+
+    let max_dim = params.max_dim.unwrap_or(DEFAULT_MAX_DIM);
+    Some(dynamic.resize(max_dim, max_dim, image::imageops::FilterType::Nearest))
+}
+
+/// Downscale to a fixed-size grayscale thumbnail for perceptual diffing.
+fn thumbnail(image: &image::DynamicImage) -> Vec<u8> {
+    image
+        .resize_exact(THUMB_DIM, THUMB_DIM, image::imageops::FilterType::Triangle)
+        .to_luma8()
+        .into_raw()
+}
+
+/// Mean absolute per-pixel difference between two equal-length thumbnails.
+fn mean_abs_diff(a: &[u8], b: &[u8]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return f64::MAX;
     }
-    buf.push_back(jpeg_bytes);
+    let sum: u64 = a
+        .iter()
+        .zip(b.iter())
+        .map(|(x, y)| x.abs_diff(*y) as u64)
+        .sum();
+    sum as f64 / a.len() as f64
+}
+
+/// Encode a [`DynamicImage`] to JPEG bytes at the given quality.
+fn encode_jpeg(image: &image::DynamicImage, quality: u8) -> Option<Vec<u8>> {
+    let mut jpeg_bytes: Vec<u8> = Vec::new();
+    let rgb = image.to_rgb8();
+    let mut encoder =
+        image::codecs::jpeg::JpegEncoder::new_with_quality(Cursor::new(&mut jpeg_bytes), quality);
+    encoder
+        .encode(rgb.as_raw(), rgb.width(), rgb.height(), image::ColorType::Rgb8.into())
+        .ok()?;
+    Some(jpeg_bytes)
 }
 
 // ---------------------------------------------------------------------------
-// Tauri command
+// Tauri commands
 // ---------------------------------------------------------------------------
 
-/// Return the current frame buffer as a list of Base64-encoded JPEG strings.
+/// Enumerate available monitors so the frontend can let the user pick which
+/// screen Claude's vision pipeline sees.
+#[tauri::command]
+pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+    match xcap::Monitor::all() {
+        Ok(monitors) => monitors
+            .into_iter()
+            .map(|m| MonitorInfo {
+                id: m.id(),
+                name: m.name().to_string(),
+                width: m.width(),
+                height: m.height(),
+                is_primary: m.is_primary(),
+            })
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Configure which displays are captured and their per-display parameters.
+#[tauri::command]
+pub fn set_capture_params(
+    targets: Option<CaptureTargets>,
+    per_display: Option<HashMap<u32, DisplayParams>>,
+) -> Result<(), String> {
+    let config = get_config();
+    let mut cfg = config.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    if let Some(t) = targets {
+        cfg.targets = t;
+    }
+    if let Some(p) = per_display {
+        cfg.per_display = p;
+    }
+    Ok(())
+}
+
+/// Resolve the primary monitor's id, falling back to the lowest id present.
+fn primary_id() -> Option<u32> {
+    let monitors = xcap::Monitor::all().ok()?;
+    monitors
+        .iter()
+        .find(|m| m.is_primary())
+        .or_else(|| monitors.iter().min_by_key(|m| m.id()))
+        .map(|m| m.id())
+}
+
+/// Return recent frames (Base64 JPEG + sequence id) for the selected display.
 ///
-/// The React frontend calls this in response to a ``screen_capture_request``
-/// message from the WebSocket, then immediately sends the frames back to
-/// Python as a ``screen_frames`` message for Claude's vision pipeline.
+/// `display` accepts `"primary"` (or absent) for the primary monitor, `"all"`
+/// to concatenate every display's frames, or a numeric monitor id.  Defaulting
+/// to the primary monitor preserves the original single-screen behavior.
 #[tauri::command]
-pub fn get_recent_frames() -> Vec<String> {
-    let binding = get_buffer();
-    let buf = match binding.lock() {
-        Ok(b) => b,
+pub fn get_recent_frames(display: Option<String>) -> Vec<CapturedFrame> {
+    let buffers = get_buffers();
+    let map = match buffers.lock() {
+        Ok(m) => m,
         Err(_) => return vec![],
     };
-    buf.iter().map(|frame| STANDARD.encode(frame)).collect()
+
+    let encode = |buf: &DisplayBuffer| -> Vec<CapturedFrame> {
+        buf.frames
+            .iter()
+            .map(|f| CapturedFrame {
+                seq: f.seq,
+                data: STANDARD.encode(&f.jpeg),
+            })
+            .collect()
+    };
+
+    let selector = display.as_deref().unwrap_or("primary");
+    match selector {
+        "all" => map.values().flat_map(|buf| encode(buf)).collect(),
+        "primary" => primary_id()
+            .and_then(|id| map.get(&id))
+            .map(encode)
+            .unwrap_or_default(),
+        other => other
+            .parse::<u32>()
+            .ok()
+            .and_then(|id| map.get(&id))
+            .map(encode)
+            .unwrap_or_default(),
+    }
 }
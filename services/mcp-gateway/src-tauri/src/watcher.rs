@@ -0,0 +1,160 @@
+//! Live project file-watcher subsystem.
+//!
+//! `watch_project` spawns a filesystem watcher for a project directory,
+//! debounces the raw OS events, and streams structured change events to the
+//! webview over a Tauri channel.  The frontend uses these to re-run
+//! `scan_security` / `search_project` incrementally and keep the security and
+//! IDE-sync panels live instead of requiring a manual refresh.
+//!
+//! Per-watch handles live in [`WatcherState`] so multiple projects can be
+//! watched concurrently and torn down cleanly via `unwatch_project`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::ipc::Channel;
+
+/// Debounce window — raw OS events for the same path within this window
+/// collapse into a single emitted change event.
+const DEBOUNCE_MS: u64 = 200;
+
+/// A structured change event delivered to the frontend.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchEvent {
+    /// `created`, `modified`, or `removed`.
+    pub kind: String,
+    /// Absolute path of the affected file or directory.
+    pub path: String,
+    /// Milliseconds since the Unix epoch when the event was emitted.
+    pub timestamp: u128,
+}
+
+/// Holds a live watcher alive for the lifetime of a watch.  Dropping it stops
+/// the underlying OS watch.
+struct WatchHandle {
+    _watcher: RecommendedWatcher,
+}
+
+/// Shared state mapping a canonical project root to its active watch handle.
+pub struct WatcherState {
+    watches: Mutex<HashMap<PathBuf, WatchHandle>>,
+}
+
+impl WatcherState {
+    pub fn new() -> Self {
+        Self {
+            watches: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// Map a raw notify event kind onto our coarse `created|modified|removed` set.
+fn classify(kind: &EventKind) -> Option<&'static str> {
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        EventKind::Modify(_) => Some("modified"),
+        EventKind::Remove(_) => Some("removed"),
+        _ => None,
+    }
+}
+
+fn now_millis() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// Start watching `project_path`, streaming debounced change events to
+/// `on_event`.  A second call for the same path replaces the existing watch.
+#[tauri::command]
+pub async fn watch_project(
+    state: tauri::State<'_, WatcherState>,
+    project_path: PathBuf,
+    on_event: Channel<WatchEvent>,
+) -> Result<(), String> {
+    if !project_path.is_absolute() {
+        return Err(format!(
+            "project_path must be absolute: '{}'",
+            project_path.display()
+        ));
+    }
+    let root = project_path
+        .canonicalize()
+        .map_err(|e| format!("Cannot canonicalize project_path: {e}"))?;
+
+    // Per-path debounce map keyed by affected path → last-emitted instant.
+    let last_emit: Mutex<HashMap<PathBuf, Instant>> = Mutex::new(HashMap::new());
+    let channel = on_event.clone();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let event = match res {
+            Ok(e) => e,
+            Err(_) => return,
+        };
+        let kind = match classify(&event.kind) {
+            Some(k) => k,
+            None => return,
+        };
+        for path in event.paths {
+            // Debounce: drop repeat events for the same path inside the window.
+            {
+                let mut seen = match last_emit.lock() {
+                    Ok(g) => g,
+                    Err(_) => return,
+                };
+                let now = Instant::now();
+                if let Some(prev) = seen.get(&path) {
+                    if now.duration_since(*prev) < Duration::from_millis(DEBOUNCE_MS) {
+                        continue;
+                    }
+                }
+                seen.insert(path.clone(), now);
+            }
+            let _ = channel.send(WatchEvent {
+                kind: kind.to_string(),
+                path: path.display().to_string(),
+                timestamp: now_millis(),
+            });
+        }
+    })
+    .map_err(|e| format!("Failed to create watcher: {e}"))?;
+
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch '{}': {e}", root.display()))?;
+
+    state
+        .watches
+        .lock()
+        .map_err(|e| format!("Lock poisoned: {e}"))?
+        .insert(root, WatchHandle { _watcher: watcher });
+
+    Ok(())
+}
+
+/// Stop watching `project_path` and release its watcher.
+#[tauri::command]
+pub async fn unwatch_project(
+    state: tauri::State<'_, WatcherState>,
+    project_path: PathBuf,
+) -> Result<(), String> {
+    let root = project_path
+        .canonicalize()
+        .unwrap_or_else(|_| project_path.clone());
+    let removed = state
+        .watches
+        .lock()
+        .map_err(|e| format!("Lock poisoned: {e}"))?
+        .remove(&root)
+        .is_some();
+    if !removed {
+        return Err(format!("No active watch for '{}'", root.display()));
+    }
+    Ok(())
+}